@@ -0,0 +1,173 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::core::components::{Admin, Ignoring, Name, OutputTx, Race, Scrollback};
+use crate::core::events::ChannelEvent;
+use crate::core::systems::deliver;
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Lines of scrollback kept per channel.
+const HISTORY_LINES: usize = 20;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelId {
+    Ooc,
+    Newbie,
+    /// Xenomorph hive-mind.
+    Hive,
+    /// Yautja clan.
+    Clan,
+    Admin,
+}
+
+impl ChannelId {
+    pub const ALL: [ChannelId; 5] = [
+        ChannelId::Ooc,
+        ChannelId::Newbie,
+        ChannelId::Hive,
+        ChannelId::Clan,
+        ChannelId::Admin,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChannelId::Ooc => "ooc",
+            ChannelId::Newbie => "newbie",
+            ChannelId::Hive => "hive",
+            ChannelId::Clan => "clan",
+            ChannelId::Admin => "admin",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChannelId::Ooc => "OOC",
+            ChannelId::Newbie => "Newbie",
+            ChannelId::Hive => "Hive",
+            ChannelId::Clan => "Clan",
+            ChannelId::Admin => "Admin",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<ChannelId> {
+        Self::ALL
+            .into_iter()
+            .find(|c| c.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Whether a character of `race` (and admin status) may use the channel at all
+    pub fn eligible(&self, race: Option<Race>, admin: bool) -> bool {
+        match self {
+            ChannelId::Ooc | ChannelId::Newbie => true,
+            ChannelId::Hive => race == Some(Race::Alien),
+            ChannelId::Clan => race == Some(Race::Predator),
+            ChannelId::Admin => admin,
+        }
+    }
+}
+
+/// A player's channel subscriptions and any sanctions against them.
+#[derive(Component, Debug, Default)]
+pub struct Channels {
+    pub joined: HashSet<ChannelId>,
+    /// May listen but not speak.
+    pub muted: HashSet<ChannelId>,
+    /// May neither listen nor speak, nor rejoin.
+    pub banned: HashSet<ChannelId>,
+}
+
+impl Channels {
+    /// Everything a new character is eligible for
+    pub fn defaults(race: Option<Race>, admin: bool) -> Self {
+        Self {
+            joined: ChannelId::ALL
+                .into_iter()
+                .filter(|c| c.eligible(race, admin))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    pub fn hears(&self, channel: ChannelId) -> bool {
+        self.joined.contains(&channel) && !self.banned.contains(&channel)
+    }
+}
+
+/// One remembered channel message, kept with its sender so ignores apply on replay.
+pub struct ChannelLine {
+    pub sender: String,
+    pub text: String,
+}
+
+/// Recent traffic on every channel.
+#[derive(Resource, Default)]
+pub struct ChannelRegistry {
+    pub history: HashMap<ChannelId, VecDeque<ChannelLine>>,
+}
+
+impl ChannelRegistry {
+    fn record(&mut self, channel: ChannelId, sender: &str, text: String) {
+        let history = self.history.entry(channel).or_default();
+        if history.len() == HISTORY_LINES {
+            history.pop_front();
+        }
+        history.push_back(ChannelLine {
+            sender: sender.to_string(),
+            text,
+        });
+    }
+}
+
+pub fn format_line(channel: ChannelId, sender: &str, message: &str) -> String {
+    format!("[{}] {}: {}", channel.label(), sender, message)
+}
+
+/// A player who may hear channel traffic.
+type Subscriber<'a> = (
+    &'a OutputTx,
+    &'a mut Scrollback,
+    &'a Channels,
+    Option<&'a Race>,
+    Has<Admin>,
+    Option<&'a Ignoring>,
+);
+
+/// Delivers channel messages to every eligible subscriber and records them in history
+pub fn flush_channels(
+    mut players: Query<Subscriber>,
+    names: Query<&Name>,
+    mut events: MessageReader<ChannelEvent>,
+    mut registry: ResMut<ChannelRegistry>,
+) {
+    for event in events.read() {
+        let sender = names.get(event.from).map_or("Someone", |n| n.0.as_str());
+        let line = format_line(event.channel, sender, &event.message);
+        for (tx, mut scrollback, channels, race, admin, ignoring) in players.iter_mut() {
+            if channels.hears(event.channel)
+                && event.channel.eligible(race.copied(), admin)
+                && !ignoring.is_some_and(|i| i.contains(sender))
+            {
+                deliver(tx, &mut scrollback, &line);
+            }
+        }
+        registry.record(event.channel, sender, line);
+    }
+}
@@ -0,0 +1,287 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+use std::fs;
+
+use crate::core::commands::reply;
+use crate::core::components::{Location, Player, RoomFlag, Zone};
+use crate::core::npc::Home;
+use crate::core::world::{GameTick, RoomRegistry, TICKS_PER_SECOND, ZoneRegistry, has_flag};
+
+use anyhow::Context;
+use bevy_ecs::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+const MINUTES_PER_DAY: u64 = 24 * 60;
+
+/// How the game clock is configured in `lib/clock.toml`.
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct ClockConfig {
+    /// Server ticks that make up one game minute.
+    pub ticks_per_minute: u64,
+    /// Hour of the first day when the server starts.
+    pub start_hour: u64,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            ticks_per_minute: TICKS_PER_SECOND,
+            start_hour: 8,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeOfDay {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+impl TimeOfDay {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TimeOfDay::Dawn => "dawn",
+            TimeOfDay::Day => "daytime",
+            TimeOfDay::Dusk => "dusk",
+            TimeOfDay::Night => "night",
+        }
+    }
+
+    /// What players outdoors see as this part of the day begins
+    fn announcement(&self) -> &'static str {
+        match self {
+            TimeOfDay::Dawn => "The sky pales as dawn breaks over the colony.",
+            TimeOfDay::Day => "The sun climbs clear of the horizon.",
+            TimeOfDay::Dusk => "The sun sinks low, staining the sky a rusty red.",
+            TimeOfDay::Night => "Night falls, and cold stars prick the sky.",
+        }
+    }
+}
+
+/// Game time, counted in minutes since the server's first day began.
+#[derive(Resource, Debug)]
+pub struct GameClock {
+    pub ticks_per_minute: u64,
+    pub minutes: u64,
+}
+
+impl Default for GameClock {
+    fn default() -> Self {
+        Self::from_config(&ClockConfig::default())
+    }
+}
+
+impl GameClock {
+    pub fn from_config(config: &ClockConfig) -> Self {
+        Self {
+            ticks_per_minute: config.ticks_per_minute.max(1),
+            minutes: config.start_hour % 24 * 60,
+        }
+    }
+
+    pub fn day(&self) -> u64 {
+        self.minutes / MINUTES_PER_DAY + 1
+    }
+
+    pub fn hour(&self) -> u64 {
+        self.minutes % MINUTES_PER_DAY / 60
+    }
+
+    pub fn minute(&self) -> u64 {
+        self.minutes % 60
+    }
+
+    pub fn time_of_day(&self) -> TimeOfDay {
+        match self.hour() {
+            5..=6 => TimeOfDay::Dawn,
+            7..=17 => TimeOfDay::Day,
+            18..=19 => TimeOfDay::Dusk,
+            _ => TimeOfDay::Night,
+        }
+    }
+}
+
+pub fn load_clock(world: &mut World, path: &str) -> anyhow::Result<()> {
+    let contents = fs::read_to_string(path).with_context(|| format!("reading {}", path))?;
+    let config: ClockConfig =
+        toml::from_str(&contents).with_context(|| format!("parsing {}", path))?;
+    world.insert_resource(GameClock::from_config(&config));
+    Ok(())
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Overcast,
+    Rain,
+    Storm,
+}
+
+impl Weather {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Weather::Clear => "The sky is clear.",
+            Weather::Overcast => "A grey blanket of cloud hangs overhead.",
+            Weather::Rain => "A steady, oily rain is falling.",
+            Weather::Storm => "A storm howls overhead, lashing everything with rain.",
+        }
+    }
+
+    /// Rolls the next hour's weather; each state only drifts to its neighbours
+    fn next(&self, rng: &mut impl Rng) -> Weather {
+        let roll: f64 = rng.random();
+        match self {
+            Weather::Clear if roll < 0.2 => Weather::Overcast,
+            Weather::Overcast if roll < 0.3 => Weather::Clear,
+            Weather::Overcast if roll < 0.6 => Weather::Rain,
+            Weather::Rain if roll < 0.3 => Weather::Overcast,
+            Weather::Rain if roll < 0.45 => Weather::Storm,
+            Weather::Storm if roll < 0.5 => Weather::Rain,
+            _ => *self,
+        }
+    }
+
+    fn change_message(&self, from: Weather) -> &'static str {
+        match (from, self) {
+            (_, Weather::Clear) => "The clouds break up and drift away.",
+            (Weather::Clear, Weather::Overcast) => "Clouds gather overhead.",
+            (_, Weather::Overcast) => "The rain eases off, leaving a sullen grey sky.",
+            (Weather::Storm, Weather::Rain) => "The storm blows itself out into a steady rain.",
+            (_, Weather::Rain) => "It begins to rain.",
+            (_, Weather::Storm) => "Thunder cracks as the rain turns into a raging storm.",
+        }
+    }
+}
+
+/// Current weather in each zone; zones not listed are clear.
+#[derive(Resource, Debug, Default)]
+pub struct ZoneWeather(pub HashMap<String, Weather>);
+
+impl ZoneWeather {
+    pub fn get(&self, zone: &str) -> Weather {
+        self.0.get(zone).copied().unwrap_or_default()
+    }
+}
+
+/// Room descriptions that replace the usual one at certain times of day.
+#[derive(Component, Debug, Default)]
+pub struct TimedDescriptions(pub HashMap<TimeOfDay, String>);
+
+/// Qualified rooms an NPC makes for at the given game hours.
+#[derive(Component, Debug, Default)]
+pub struct Schedule(pub Vec<(u64, String)>);
+
+/// The description of `room` for the current time of day
+pub fn room_description(world: &World, room: Entity, base: &str) -> String {
+    let now = world.resource::<GameClock>().time_of_day();
+    world
+        .get::<TimedDescriptions>(room)
+        .and_then(|t| t.0.get(&now))
+        .map_or(base.to_string(), |d| d.clone())
+}
+
+/// Whether `room` lies under the open sky
+pub fn is_outdoors(world: &World, room: Entity) -> bool {
+    !has_flag(world, room, RoomFlag::Indoors)
+}
+
+/// Tells every player outdoors, optionally only within `zone`
+fn announce_outdoors(world: &mut World, zone: Option<&str>, text: &str) {
+    let listeners: Vec<Entity> = world
+        .query_filtered::<(Entity, &Location), With<Player>>()
+        .iter(world)
+        .filter(|(_, loc)| is_outdoors(world, loc.0))
+        .filter(|(_, loc)| {
+            zone.is_none_or(|z| world.get::<Zone>(loc.0).is_some_and(|rz| rz.0 == z))
+        })
+        .map(|(ent, _)| ent)
+        .collect();
+    for player in listeners {
+        reply(world, player, text.to_string());
+    }
+}
+
+/// Advances game time by a minute whenever enough ticks pass, running the hourly weather and schedules
+pub fn advance_clock(world: &mut World) {
+    let tick = world.resource::<GameTick>().0;
+    let mut clock = world.resource_mut::<GameClock>();
+    if tick == 0 || !tick.is_multiple_of(clock.ticks_per_minute) {
+        return;
+    }
+    let before = clock.time_of_day();
+    clock.minutes += 1;
+    let now = clock.time_of_day();
+    let new_hour = clock.minute() == 0;
+    let hour = clock.hour();
+
+    if now != before {
+        announce_outdoors(world, None, now.announcement());
+    }
+    if new_hour {
+        change_weather(world);
+        follow_schedules(world, hour);
+    }
+}
+
+fn change_weather(world: &mut World) {
+    let mut zones: Vec<String> = world
+        .resource::<ZoneRegistry>()
+        .id_to_name
+        .keys()
+        .cloned()
+        .collect();
+    zones.sort();
+    let mut rng = rand::rng();
+    for zone in zones {
+        let from = world.resource::<ZoneWeather>().get(&zone);
+        let to = from.next(&mut rng);
+        if to == from {
+            continue;
+        }
+        world
+            .resource_mut::<ZoneWeather>()
+            .0
+            .insert(zone.clone(), to);
+        announce_outdoors(world, Some(&zone), to.change_message(from));
+    }
+}
+
+/// Moves the post of every NPC whose schedule names this hour
+fn follow_schedules(world: &mut World, hour: u64) {
+    let moves: Vec<(Entity, String)> = world
+        .query::<(Entity, &Schedule)>()
+        .iter(world)
+        .filter_map(|(ent, schedule)| {
+            let (_, room) = schedule.0.iter().find(|(h, _)| *h == hour)?;
+            Some((ent, room.clone()))
+        })
+        .collect();
+    for (npc, room) in moves {
+        if let Some(room) = world.resource::<RoomRegistry>().get(&room)
+            && let Some(mut home) = world.get_mut::<Home>(npc)
+        {
+            home.0 = room;
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::components::{Attributes, Health, Location, Name};
+use crate::core::events::{BroadcastRoomEvent, DamageEvent, OutputEvent};
+use crate::core::text::capitalize;
+use crate::core::world::{GameTick, TICKS_PER_SECOND};
+
+use bevy_ecs::prelude::*;
+use rand::Rng;
+
+/// Two seconds.
+pub const COMBAT_ROUND_TICKS: u64 = 2 * TICKS_PER_SECOND;
+
+/// The entity this combatant is currently trading blows with.
+#[derive(Component)]
+pub struct Fighting(pub Entity);
+
+type Combatant<'a> = (
+    Entity,
+    &'a Fighting,
+    &'a Location,
+    Option<&'a Attributes>,
+    Option<&'a Name>,
+);
+
+/// Resolves one melee exchange for everyone engaged, every combat round
+pub fn combat_rounds(
+    mut commands: Commands,
+    tick: Res<GameTick>,
+    fighters: Query<Combatant>,
+    targets: Query<(&Location, &Health, Option<&Name>, Has<Fighting>)>,
+    mut damage: MessageWriter<DamageEvent>,
+    mut output: MessageWriter<OutputEvent>,
+    mut room_events: MessageWriter<BroadcastRoomEvent>,
+) {
+    if !tick.0.is_multiple_of(COMBAT_ROUND_TICKS) {
+        return;
+    }
+
+    let mut rng = rand::rng();
+    for (ent, fighting, loc, attrs, name) in fighters.iter() {
+        let target = fighting.0;
+        let Ok((target_loc, target_health, target_name, target_fighting)) = targets.get(target)
+        else {
+            commands.entity(ent).remove::<Fighting>();
+            continue;
+        };
+        if target_loc.0 != loc.0 || target_health.current == 0 {
+            commands.entity(ent).remove::<Fighting>();
+            continue;
+        }
+
+        let strength = attrs.map_or(10, |a| a.strength);
+        let amount = 2 + strength / 3 + rng.random_range(0..=3);
+        let name = name.map_or("Someone".to_string(), |n| capitalize(&n.0));
+        let target_name = target_name.map_or("someone".to_string(), |n| n.0.clone());
+
+        output.write(OutputEvent {
+            player: ent,
+            text: format!("You hit {}.", target_name),
+        });
+        room_events.write(BroadcastRoomEvent {
+            from: ent,
+            room: loc.0,
+            text: format!("{} hits {}.", name, target_name),
+            unseen: Some(format!("Someone hits {}.", target_name)),
+        });
+        damage.write(DamageEvent {
+            source: Some(ent),
+            target,
+            amount,
+        });
+
+        if !target_fighting {
+            commands.entity(target).insert(Fighting(ent));
+        }
+    }
+}
@@ -0,0 +1,345 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::{find_in_room, reply, spend_stamina};
+use crate::core::components::{Attributes, Cloaked, Cooldowns, Health, Hidden, Location, Race};
+use crate::core::effects::{EffectKind, has_effect};
+use crate::core::environment::forbid_violence;
+use crate::core::events::{ApplyEffectEvent, BroadcastRoomEvent, DamageEvent};
+use crate::core::race::RaceTrait;
+use crate::core::stealth::reveal;
+use crate::core::text::display_name;
+use crate::core::world::{GameTick, TICKS_PER_SECOND};
+
+use bevy_ecs::prelude::*;
+
+const CLOAK_STAMINA: u32 = 5;
+const FLARE_COOLDOWN: u64 = 30 * TICKS_PER_SECOND;
+const FLARE_STAMINA: u32 = 15;
+const FEAR_DURATION: u64 = 5 * TICKS_PER_SECOND;
+const HISS_COOLDOWN: u64 = 15 * TICKS_PER_SECOND;
+const HISS_STAMINA: u32 = 10;
+const MEDKIT_COOLDOWN: u64 = 60 * TICKS_PER_SECOND;
+const MEDKIT_DURATION: u64 = 10 * TICKS_PER_SECOND;
+const MEDKIT_HEALING: u32 = 3;
+const PLASMA_COOLDOWN: u64 = 5 * TICKS_PER_SECOND;
+const PLASMA_STAMINA: u32 = 10;
+
+fn require_trait(world: &mut World, player: Entity, t: RaceTrait, refusal: &str) -> bool {
+    let has = world
+        .get::<Race>(player)
+        .is_some_and(|race| race.has_trait(t));
+    if !has {
+        reply(world, player, refusal.to_string());
+    }
+    has
+}
+
+fn on_cooldown(world: &mut World, player: Entity, ability: &'static str) -> bool {
+    let now = world.resource::<GameTick>().0;
+    let ready_at = world
+        .get::<Cooldowns>(player)
+        .and_then(|c| c.0.get(ability).copied())
+        .unwrap_or(0);
+    if now < ready_at {
+        reply(
+            world,
+            player,
+            format!(
+                "You must wait {} more seconds before using {} again.",
+                (ready_at - now).div_ceil(TICKS_PER_SECOND),
+                ability
+            ),
+        );
+        return true;
+    }
+    false
+}
+
+fn start_cooldown(world: &mut World, player: Entity, ability: &'static str, ticks: u64) {
+    let ready_at = world.resource::<GameTick>().0 + ticks;
+    if let Some(mut cooldowns) = world.get_mut::<Cooldowns>(player) {
+        cooldowns.0.insert(ability, ready_at);
+    } else {
+        let mut cooldowns = Cooldowns::default();
+        cooldowns.0.insert(ability, ready_at);
+        world.entity_mut(player).insert(cooldowns);
+    }
+}
+
+pub fn cmd_cloak(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    if !require_trait(
+        world,
+        player,
+        RaceTrait::Cloaking,
+        "You have no cloaking device.",
+    ) {
+        return;
+    }
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    let name = display_name(world, player);
+
+    if world.get::<Cloaked>(player).is_some() {
+        world.entity_mut(player).remove::<Cloaked>();
+        reply(
+            world,
+            player,
+            "You deactivate your cloaking device.".to_string(),
+        );
+        world.write_message(BroadcastRoomEvent {
+            from: player,
+            room,
+            text: format!("The air ripples and {} shimmers into view.", name),
+            unseen: Some("The air ripples and someone shimmers into view.".to_string()),
+        });
+        return;
+    }
+
+    if !spend_stamina(world, player, CLOAK_STAMINA) {
+        return;
+    }
+    world.entity_mut(player).insert(Cloaked);
+    reply(
+        world,
+        player,
+        "You activate your cloaking device and fade from sight.".to_string(),
+    );
+    world.write_message(BroadcastRoomEvent {
+        from: player,
+        room,
+        text: format!("{} shimmers and vanishes.", name),
+        unseen: Some("Someone shimmers and vanishes.".to_string()),
+    });
+}
+
+pub fn cmd_flare(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    if !require_trait(
+        world,
+        player,
+        RaceTrait::ToolUse,
+        "You wouldn't know what to do with a flare.",
+    ) {
+        return;
+    }
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    if on_cooldown(world, player, "flare") || !spend_stamina(world, player, FLARE_STAMINA) {
+        return;
+    }
+    start_cooldown(world, player, "flare", FLARE_COOLDOWN);
+
+    let name = display_name(world, player);
+    reply(
+        world,
+        player,
+        "You strike a flare and hold it high.".to_string(),
+    );
+    world.write_message(BroadcastRoomEvent {
+        from: player,
+        room,
+        text: format!(
+            "{} ignites a flare, bathing the room in harsh red light!",
+            name
+        ),
+        unseen: Some("Someone ignites a flare, bathing the room in harsh red light!".to_string()),
+    });
+
+    let revealed: Vec<(Entity, bool)> = world
+        .query::<(Entity, &Location, Has<Cloaked>, Has<Hidden>)>()
+        .iter(world)
+        .filter(|(_, loc, cloaked, hidden)| loc.0 == room && (*cloaked || *hidden))
+        .map(|(ent, _, cloaked, _)| (ent, cloaked))
+        .collect();
+    for (ent, cloaked) in revealed {
+        world.entity_mut(ent).remove::<(Cloaked, Hidden)>();
+        let revealed_name = display_name(world, ent);
+        let text = if cloaked {
+            "Your cloak flickers and fails in the glare!"
+        } else {
+            "The glare leaves you nowhere to hide!"
+        };
+        reply(world, ent, text.to_string());
+        world.write_message(BroadcastRoomEvent {
+            from: ent,
+            room,
+            text: format!("The glare reveals {} crouching nearby!", revealed_name),
+            unseen: Some("The glare reveals someone crouching nearby!".to_string()),
+        });
+    }
+}
+
+pub fn cmd_hiss(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    if !require_trait(
+        world,
+        player,
+        RaceTrait::Terrify,
+        "You hiss quietly. Nobody is impressed.",
+    ) {
+        return;
+    }
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    if on_cooldown(world, player, "hiss") || !spend_stamina(world, player, HISS_STAMINA) {
+        return;
+    }
+    start_cooldown(world, player, "hiss", HISS_COOLDOWN);
+
+    let name = display_name(world, player);
+    reply(
+        world,
+        player,
+        "You rear back and let out a blood-curdling hiss!".to_string(),
+    );
+    world.write_message(BroadcastRoomEvent {
+        from: player,
+        room,
+        text: format!("{} rears back and lets out a blood-curdling hiss!", name),
+        unseen: Some("Someone rears back and lets out a blood-curdling hiss!".to_string()),
+    });
+
+    let victims: Vec<Entity> = world
+        .query::<(Entity, &Location, &Race)>()
+        .iter(world)
+        .filter(|(ent, loc, race)| *ent != player && loc.0 == room && **race != Race::Alien)
+        .map(|(ent, _, _)| ent)
+        .collect();
+    for ent in victims {
+        world.write_message(ApplyEffectEvent {
+            target: ent,
+            kind: EffectKind::Frightened,
+            duration: FEAR_DURATION,
+            magnitude: 0,
+            source: Some(player),
+        });
+    }
+}
+
+pub fn cmd_medkit(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    if !require_trait(
+        world,
+        player,
+        RaceTrait::ToolUse,
+        "You fumble with the bandages uselessly.",
+    ) {
+        return;
+    }
+    if on_cooldown(world, player, "medkit") {
+        return;
+    }
+    start_cooldown(world, player, "medkit", MEDKIT_COOLDOWN);
+
+    reply(
+        world,
+        player,
+        "You patch your wounds and inject a dose of stimulant.".to_string(),
+    );
+    if let Some(room) = world.get::<Location>(player).map(|l| l.0) {
+        let name = display_name(world, player);
+        world.write_message(BroadcastRoomEvent {
+            from: player,
+            room,
+            text: format!("{} patches up their wounds.", name),
+            unseen: Some("Someone patches up their wounds.".to_string()),
+        });
+    }
+    world.write_message(ApplyEffectEvent {
+        target: player,
+        kind: EffectKind::Regenerating,
+        duration: MEDKIT_DURATION,
+        magnitude: MEDKIT_HEALING,
+        source: Some(player),
+    });
+}
+
+pub fn cmd_plasma(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    if !require_trait(
+        world,
+        player,
+        RaceTrait::PlasmaCaster,
+        "You have no plasma caster.",
+    ) {
+        return;
+    }
+    if has_effect(world, player, EffectKind::Frightened) {
+        reply(
+            world,
+            player,
+            "You are too terrified to take aim.".to_string(),
+        );
+        return;
+    }
+    if args.is_empty() {
+        reply(world, player, "Fire at whom?".to_string());
+        return;
+    }
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    if forbid_violence(world, player, room) {
+        return;
+    }
+    let target = match find_in_room(world, room, args[0], player) {
+        Some(t) if world.get::<Health>(t).is_some() => t,
+        _ => {
+            reply(world, player, "You don't see that here.".to_string());
+            return;
+        }
+    };
+    if on_cooldown(world, player, "plasma") || !spend_stamina(world, player, PLASMA_STAMINA) {
+        return;
+    }
+    start_cooldown(world, player, "plasma", PLASMA_COOLDOWN);
+    reveal(world, player);
+
+    if world.get::<Cloaked>(player).is_some() {
+        world.entity_mut(player).remove::<Cloaked>();
+        reply(
+            world,
+            player,
+            "Your cloak flickers off as the caster discharges.".to_string(),
+        );
+    }
+
+    let intellect = world.get::<Attributes>(player).map_or(0, |a| a.intellect);
+    let name = display_name(world, player);
+    let target_name = display_name(world, target);
+    reply(
+        world,
+        player,
+        format!("You fire your plasma caster at {}!", target_name),
+    );
+    world.write_message(BroadcastRoomEvent {
+        from: player,
+        room,
+        text: format!(
+            "{} fires a searing bolt of plasma at {}!",
+            name, target_name
+        ),
+        unseen: Some(format!(
+            "Someone fires a searing bolt of plasma at {}!",
+            target_name
+        )),
+    });
+    world.write_message(DamageEvent {
+        source: Some(player),
+        target,
+        amount: 15 + intellect,
+    });
+}
@@ -0,0 +1,257 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::channels::{ChannelId, ChannelRegistry, Channels};
+use crate::core::commands::{find_player, is_muted, reply};
+use crate::core::components::{Admin, Ignoring, Name, Race};
+use crate::core::events::{ChannelEvent, SaveEvent};
+
+use bevy_ecs::prelude::*;
+
+fn eligible(world: &World, player: Entity, channel: ChannelId) -> bool {
+    channel.eligible(
+        world.get::<Race>(player).copied(),
+        world.get::<Admin>(player).is_some(),
+    )
+}
+
+/// Speaks on, or shows the history of, `channel`
+fn channel_command(channel: ChannelId, player: Entity, world: &mut World, args: &[&str]) {
+    if !eligible(world, player, channel) {
+        reply(
+            world,
+            player,
+            "You have no access to that channel.".to_string(),
+        );
+        return;
+    }
+    let Some(subs) = world.get::<Channels>(player) else {
+        return;
+    };
+    if subs.banned.contains(&channel) {
+        reply(
+            world,
+            player,
+            format!("You are banned from {}.", channel.label()),
+        );
+        return;
+    }
+    if !subs.joined.contains(&channel) {
+        reply(
+            world,
+            player,
+            format!(
+                "You aren't on {}. Type 'join {}' first.",
+                channel.label(),
+                channel.name()
+            ),
+        );
+        return;
+    }
+
+    if args.is_empty() {
+        reply(world, player, format!("Say what on {}?", channel.label()));
+        return;
+    }
+    if args.len() == 1 && args[0].eq_ignore_ascii_case("history") {
+        let ignoring = world.get::<Ignoring>(player);
+        let lines: Vec<String> = world
+            .resource::<ChannelRegistry>()
+            .history
+            .get(&channel)
+            .map(|h| {
+                h.iter()
+                    .filter(|l| !ignoring.is_some_and(|i| i.contains(&l.sender)))
+                    .map(|l| l.text.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let text = if lines.is_empty() {
+            format!("Nothing has been said on {} lately.", channel.label())
+        } else {
+            lines.join("\n")
+        };
+        reply(world, player, text);
+        return;
+    }
+
+    // Only speaking is muted; reading history above stays open.
+    if is_muted(world, player) {
+        return;
+    }
+    if world
+        .get::<Channels>(player)
+        .is_some_and(|c| c.muted.contains(&channel))
+    {
+        reply(
+            world,
+            player,
+            format!("You are muted on {}.", channel.label()),
+        );
+        return;
+    }
+    world.write_message(ChannelEvent {
+        from: player,
+        channel,
+        message: args.join(" "),
+    });
+}
+
+pub fn cmd_admin(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    channel_command(ChannelId::Admin, player, world, args);
+}
+
+pub fn cmd_clan(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    channel_command(ChannelId::Clan, player, world, args);
+}
+
+pub fn cmd_hive(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    channel_command(ChannelId::Hive, player, world, args);
+}
+
+pub fn cmd_newbie(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    channel_command(ChannelId::Newbie, player, world, args);
+}
+
+pub fn cmd_ooc(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    channel_command(ChannelId::Ooc, player, world, args);
+}
+
+pub fn cmd_channels(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let Some(subs) = world.get::<Channels>(player) else {
+        return;
+    };
+    let lines: Vec<String> = ChannelId::ALL
+        .into_iter()
+        .filter(|c| eligible(world, player, *c))
+        .map(|c| {
+            let status = if subs.banned.contains(&c) {
+                "banned"
+            } else if !subs.joined.contains(&c) {
+                "off"
+            } else if subs.muted.contains(&c) {
+                "on (muted)"
+            } else {
+                "on"
+            };
+            format!("  {:<8} {}", c.name(), status)
+        })
+        .collect();
+    reply(world, player, format!("Channels:\n{}", lines.join("\n")));
+}
+
+/// The channel named by `args[0]`, if the player may use it
+fn pick_channel(world: &mut World, player: Entity, args: &[&str]) -> Option<ChannelId> {
+    let channel = args
+        .first()
+        .and_then(|a| ChannelId::parse(a))
+        .filter(|c| eligible(world, player, *c));
+    if channel.is_none() {
+        reply(world, player, "No such channel.".to_string());
+    }
+    channel
+}
+
+pub fn cmd_join(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(channel) = pick_channel(world, player, args) else {
+        return;
+    };
+    let Some(mut subs) = world.get_mut::<Channels>(player) else {
+        return;
+    };
+    let text = if subs.banned.contains(&channel) {
+        format!("You are banned from {}.", channel.label())
+    } else if subs.joined.insert(channel) {
+        format!("You join {}.", channel.label())
+    } else {
+        format!("You are already on {}.", channel.label())
+    };
+    reply(world, player, text);
+    world.write_message(SaveEvent { player });
+}
+
+pub fn cmd_leave(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(channel) = pick_channel(world, player, args) else {
+        return;
+    };
+    let Some(mut subs) = world.get_mut::<Channels>(player) else {
+        return;
+    };
+    let text = if subs.joined.remove(&channel) {
+        format!("You leave {}.", channel.label())
+    } else {
+        format!("You aren't on {}.", channel.label())
+    };
+    reply(world, player, text);
+    world.write_message(SaveEvent { player });
+}
+
+#[derive(Copy, Clone)]
+enum Sanction {
+    Mute,
+    Ban,
+}
+
+/// Toggles a mute or ban on an online player for one channel
+fn sanction(kind: Sanction, player: Entity, world: &mut World, args: &[&str]) {
+    let (Some(channel), Some(target)) =
+        (args.first().and_then(|a| ChannelId::parse(a)), args.get(1))
+    else {
+        reply(world, player, "Usage: <channel> <player>".to_string());
+        return;
+    };
+    let Some(victim) = find_player(world, target) else {
+        reply(world, player, format!("{} is not online.", target));
+        return;
+    };
+    let name = world
+        .get::<Name>(victim)
+        .map_or(target.to_string(), |n| n.0.clone());
+    let Some(mut subs) = world.get_mut::<Channels>(victim) else {
+        return;
+    };
+    let (set, verb) = match kind {
+        Sanction::Mute => (&mut subs.muted, "muted"),
+        Sanction::Ban => (&mut subs.banned, "banned"),
+    };
+    let added = set.insert(channel);
+    if !added {
+        set.remove(&channel);
+    }
+
+    let (mine, theirs) = if added {
+        (
+            format!("{} is now {} on {}.", name, verb, channel.label()),
+            format!("You have been {} on {}.", verb, channel.label()),
+        )
+    } else {
+        (
+            format!("{} is no longer {} on {}.", name, verb, channel.label()),
+            format!("You are no longer {} on {}.", verb, channel.label()),
+        )
+    };
+    reply(world, player, mine);
+    reply(world, victim, theirs);
+    world.write_message(SaveEvent { player: victim });
+}
+
+pub fn cmd_chanban(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    sanction(Sanction::Ban, player, world, args);
+}
+
+pub fn cmd_chanmute(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    sanction(Sanction::Mute, player, world, args);
+}
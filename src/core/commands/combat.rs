@@ -0,0 +1,65 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::combat::Fighting;
+use crate::core::commands::{find_in_room, reply};
+use crate::core::components::{Health, Location, Name};
+use crate::core::environment::forbid_violence;
+use crate::core::events::BroadcastRoomEvent;
+use crate::core::stealth::reveal;
+use crate::core::text::display_name;
+
+use bevy_ecs::prelude::*;
+
+pub fn cmd_kill(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(target) = args.first() else {
+        reply(world, player, "Attack whom?".to_string());
+        return;
+    };
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    if forbid_violence(world, player, room) {
+        return;
+    }
+    if world.get::<Fighting>(player).is_some() {
+        reply(world, player, "You are already fighting!".to_string());
+        return;
+    }
+
+    let Some(victim) =
+        find_in_room(world, room, target, player).filter(|v| world.get::<Health>(*v).is_some())
+    else {
+        reply(world, player, "They aren't here.".to_string());
+        return;
+    };
+
+    reveal(world, player);
+    let name = display_name(world, player);
+    let victim_name = match world.get::<Name>(victim) {
+        Some(n) => n.0.clone(),
+        None => "someone".to_string(),
+    };
+
+    world.entity_mut(player).insert(Fighting(victim));
+    reply(world, player, format!("You attack {}!", victim_name));
+    world.write_message(BroadcastRoomEvent {
+        from: player,
+        room,
+        text: format!("{} attacks {}!", name, victim_name),
+        unseen: Some(format!("Someone attacks {}!", victim_name)),
+    });
+}
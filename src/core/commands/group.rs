@@ -0,0 +1,382 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::{find_in_room, find_player, is_muted, reply};
+use crate::core::components::{Health, Location, Player, Room};
+use crate::core::groups::{
+    Following, GroupInvite, GroupLeader, GroupMember, LootRule, MAX_GROUP_SIZE, leader_of,
+    leave_group, members, stop_following, tell_group,
+};
+use crate::core::text::{capitalize, display_name};
+
+use bevy_ecs::prelude::*;
+
+pub fn cmd_follow(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(target) = args.first() else {
+        if world.get::<Following>(player).is_some() {
+            stop_following(world, player);
+        } else {
+            reply(world, player, "Follow whom?".to_string());
+        }
+        return;
+    };
+    let name = display_name(world, player);
+    if target.eq_ignore_ascii_case("self")
+        || target.eq_ignore_ascii_case("me")
+        || target.eq_ignore_ascii_case(&name)
+    {
+        if world.get::<Following>(player).is_some() {
+            stop_following(world, player);
+        } else {
+            reply(world, player, "You aren't following anyone.".to_string());
+        }
+        return;
+    }
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    let Some(leader) =
+        find_in_room(world, room, target, player).filter(|t| world.get::<Player>(*t).is_some())
+    else {
+        reply(world, player, "They aren't here.".to_string());
+        return;
+    };
+
+    let mut ahead = Some(leader);
+    while let Some(ent) = ahead {
+        if ent == player {
+            reply(
+                world,
+                player,
+                "You can't follow someone who is following you.".to_string(),
+            );
+            return;
+        }
+        ahead = world.get::<Following>(ent).map(|f| f.0);
+    }
+
+    let leader_name = display_name(world, leader);
+    match world.get::<Following>(player).map(|f| f.0) {
+        Some(current) if current == leader => {
+            reply(
+                world,
+                player,
+                format!("You are already following {}.", leader_name),
+            );
+            return;
+        }
+        Some(_) => stop_following(world, player),
+        None => {}
+    }
+    world.entity_mut(player).insert(Following(leader));
+    reply(world, player, format!("You now follow {}.", leader_name));
+    reply(world, leader, format!("{} now follows you.", name));
+}
+
+pub fn cmd_group(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let sub = args.first().map(|a| a.to_lowercase());
+    match sub.as_deref() {
+        None | Some("status") => show_group(world, player),
+        Some("invite") => match args.get(1) {
+            Some(target) => invite(world, player, target),
+            None => reply(world, player, "Usage: group invite <player>".to_string()),
+        },
+        Some("accept") => accept(world, player),
+        Some("decline") => decline(world, player),
+        Some("leave") => {
+            if leader_of(world, player).is_some() {
+                leave_group(world, player);
+            } else {
+                reply(world, player, "You aren't in a group.".to_string());
+            }
+        }
+        Some("kick") => match args.get(1) {
+            Some(target) => kick(world, player, target),
+            None => reply(world, player, "Usage: group kick <player>".to_string()),
+        },
+        Some("loot") => set_loot(world, player, args.get(1).copied()),
+        Some("disband") => disband(world, player),
+        _ => reply(
+            world,
+            player,
+            "Usage: group [status|invite <player>|accept|decline|leave|kick <player>|loot <free|roundrobin|leader>|disband]"
+                .to_string(),
+        ),
+    }
+}
+
+pub fn cmd_gtell(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    if leader_of(world, player).is_none() {
+        reply(world, player, "You aren't in a group.".to_string());
+        return;
+    }
+    if args.is_empty() {
+        reply(world, player, "Tell your group what?".to_string());
+        return;
+    }
+    if is_muted(world, player) {
+        return;
+    }
+    let name = display_name(world, player);
+    tell_group(
+        world,
+        player,
+        &format!("[Group] {}: {}", name, args.join(" ")),
+    );
+}
+
+/// Whether `player` leads its group, complaining if not
+fn require_leader(world: &mut World, player: Entity) -> bool {
+    match leader_of(world, player) {
+        Some(leader) if leader == player => true,
+        Some(_) => {
+            reply(
+                world,
+                player,
+                "Only the group leader can do that.".to_string(),
+            );
+            false
+        }
+        None => {
+            reply(world, player, "You aren't in a group.".to_string());
+            false
+        }
+    }
+}
+
+fn show_group(world: &mut World, player: Entity) {
+    let mut lines = Vec::new();
+    if let Some(leader) = leader_of(world, player) {
+        let loot = world
+            .get::<GroupLeader>(leader)
+            .map_or(LootRule::Free, |g| g.loot);
+        lines.push(format!(
+            "Your group, led by {} (loot: {}):",
+            display_name(world, leader),
+            loot.label()
+        ));
+        for member in members(world, leader) {
+            let room = world
+                .get::<Location>(member)
+                .and_then(|l| world.get::<Room>(l.0))
+                .map_or("somewhere".to_string(), |r| r.name.clone());
+            let health = world
+                .get::<Health>(member)
+                .map_or("?".to_string(), |h| format!("{}/{} hp", h.current, h.max));
+            lines.push(format!(
+                "  {:<16} {:<20} {}",
+                display_name(world, member),
+                room,
+                health
+            ));
+        }
+    } else {
+        lines.push("You aren't in a group.".to_string());
+    }
+    if let Some(Following(target)) = world.get::<Following>(player).copied() {
+        lines.push(format!(
+            "You are following {}.",
+            display_name(world, target)
+        ));
+    }
+    reply(world, player, lines.join("\n"));
+}
+
+fn invite(world: &mut World, player: Entity, target: &str) {
+    let leader = leader_of(world, player);
+    if leader.is_some_and(|l| l != player) {
+        reply(
+            world,
+            player,
+            "Only the group leader can invite new members.".to_string(),
+        );
+        return;
+    }
+    let Some(invitee) = find_player(world, target) else {
+        reply(
+            world,
+            player,
+            format!("There is no one named {} online.", capitalize(target)),
+        );
+        return;
+    };
+    if invitee == player {
+        reply(world, player, "You can't invite yourself.".to_string());
+        return;
+    }
+    let invitee_name = display_name(world, invitee);
+    if leader_of(world, invitee).is_some() {
+        reply(
+            world,
+            player,
+            format!("{} is already in a group.", invitee_name),
+        );
+        return;
+    }
+    if leader.is_some() && members(world, player).len() >= MAX_GROUP_SIZE {
+        reply(world, player, "Your group is full.".to_string());
+        return;
+    }
+
+    world.entity_mut(invitee).insert(GroupInvite(player));
+    let name = display_name(world, player);
+    reply(
+        world,
+        player,
+        format!("You invite {} to join your group.", invitee_name),
+    );
+    reply(
+        world,
+        invitee,
+        format!(
+            "{} invites you to join their group. Type 'group accept' or 'group decline'.",
+            name
+        ),
+    );
+}
+
+fn accept(world: &mut World, player: Entity) {
+    let Some(GroupInvite(inviter)) = world.entity_mut(player).take::<GroupInvite>() else {
+        reply(
+            world,
+            player,
+            "No one has invited you to a group.".to_string(),
+        );
+        return;
+    };
+    if leader_of(world, player).is_some() {
+        reply(world, player, "You are already in a group.".to_string());
+        return;
+    }
+    let still_leading =
+        world.get_entity(inviter).is_ok() && leader_of(world, inviter).is_none_or(|l| l == inviter);
+    if !still_leading {
+        reply(
+            world,
+            player,
+            "That invitation is no longer valid.".to_string(),
+        );
+        return;
+    }
+    if members(world, inviter).len() >= MAX_GROUP_SIZE {
+        reply(world, player, "That group is full.".to_string());
+        return;
+    }
+
+    if leader_of(world, inviter).is_none() {
+        world
+            .entity_mut(inviter)
+            .insert((GroupMember(inviter), GroupLeader::default()));
+    }
+    world.entity_mut(player).insert(GroupMember(inviter));
+    let name = display_name(world, player);
+    tell_group(world, player, &format!("{} has joined the group.", name));
+}
+
+fn decline(world: &mut World, player: Entity) {
+    let Some(GroupInvite(inviter)) = world.entity_mut(player).take::<GroupInvite>() else {
+        reply(
+            world,
+            player,
+            "No one has invited you to a group.".to_string(),
+        );
+        return;
+    };
+    let name = display_name(world, player);
+    let inviter_name = display_name(world, inviter);
+    reply(
+        world,
+        player,
+        format!("You decline {}'s invitation.", inviter_name),
+    );
+    if world.get_entity(inviter).is_ok() {
+        reply(
+            world,
+            inviter,
+            format!("{} declines your invitation.", name),
+        );
+    }
+}
+
+fn kick(world: &mut World, player: Entity, target: &str) {
+    if !require_leader(world, player) {
+        return;
+    }
+    let Some(member) = members(world, player)
+        .into_iter()
+        .find(|m| *m != player && display_name(world, *m).eq_ignore_ascii_case(target))
+    else {
+        reply(
+            world,
+            player,
+            format!("{} isn't in your group.", capitalize(target)),
+        );
+        return;
+    };
+    let name = display_name(world, player);
+    reply(
+        world,
+        member,
+        format!("{} removes you from the group.", name),
+    );
+    leave_group(world, member);
+}
+
+fn set_loot(world: &mut World, player: Entity, rule: Option<&str>) {
+    if !require_leader(world, player) {
+        return;
+    }
+    let Some(rule) = rule else {
+        let current = world
+            .get::<GroupLeader>(player)
+            .map_or(LootRule::Free, |g| g.loot);
+        reply(
+            world,
+            player,
+            format!(
+                "Loot is {}. Choose free, roundrobin or leader.",
+                current.label()
+            ),
+        );
+        return;
+    };
+    let Some(rule) = LootRule::parse(&rule.to_lowercase()) else {
+        reply(
+            world,
+            player,
+            "Loot rules are free, roundrobin and leader.".to_string(),
+        );
+        return;
+    };
+    if let Some(mut group) = world.get_mut::<GroupLeader>(player) {
+        group.loot = rule;
+        group.next = 0;
+    }
+    tell_group(world, player, &format!("Loot is now {}.", rule.label()));
+}
+
+fn disband(world: &mut World, player: Entity) {
+    if !require_leader(world, player) {
+        return;
+    }
+    let name = display_name(world, player);
+    tell_group(world, player, &format!("{} disbands the group.", name));
+    for member in members(world, player) {
+        world
+            .entity_mut(member)
+            .remove::<(GroupMember, GroupLeader)>();
+    }
+}
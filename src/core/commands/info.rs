@@ -0,0 +1,197 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::clock::{GameClock, ZoneWeather, is_outdoors};
+use crate::core::commands::reply;
+use crate::core::components::{
+    Attributes, Credits, Experience, Health, Location, Name, Player, PlayerState, Race, Stamina,
+    Zone,
+};
+use crate::core::effects::{Effects, Stacking};
+use crate::core::progression::{MAX_LEVEL, next_level_xp};
+use crate::core::quests::{QuestLog, QuestRegistry};
+use crate::core::race::RaceTrait;
+use crate::core::visibility::can_see;
+use crate::core::world::{TICKS_PER_SECOND, ZoneRegistry};
+
+use bevy_ecs::prelude::*;
+
+pub fn cmd_affects(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let lines: Vec<String> = world
+        .get::<Effects>(player)
+        .map(|effects| {
+            effects
+                .0
+                .iter()
+                .map(|e| {
+                    let stacks = match e.kind.stacking() {
+                        Stacking::Intensify { .. } if e.stacks > 1 => format!(" x{}", e.stacks),
+                        _ => String::new(),
+                    };
+                    format!(
+                        "  {}{} ({}s remaining)",
+                        e.kind.label(),
+                        stacks,
+                        e.remaining.div_ceil(TICKS_PER_SECOND)
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let text = if lines.is_empty() {
+        "You are not affected by anything.".to_string()
+    } else {
+        format!("You are affected by:\n{}", lines.join("\n"))
+    };
+    reply(world, player, text);
+}
+
+pub fn cmd_score(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let (Some(name), Some(race), Some(attrs), Some(health), Some(exp)) = (
+        world.get::<Name>(player),
+        world.get::<Race>(player),
+        world.get::<Attributes>(player),
+        world.get::<Health>(player),
+        world.get::<Experience>(player),
+    ) else {
+        reply(world, player, "You have no character yet.".to_string());
+        return;
+    };
+
+    let next = if exp.level >= MAX_LEVEL {
+        "max".to_string()
+    } else {
+        next_level_xp(exp.level).to_string()
+    };
+    let stamina = match (
+        race.has_trait(RaceTrait::FatigueImmune),
+        world.get::<Stamina>(player),
+    ) {
+        (true, _) => "tireless".to_string(),
+        (false, Some(s)) => format!("{}/{}", s.current, s.max),
+        (false, None) => "-".to_string(),
+    };
+
+    let text = format!(
+        "{} the {}, level {}\n\
+         Experience: {} / {}\n\
+         Health: {}/{}   Stamina: {}\n\
+         Strength: {}   Agility: {}   Intellect: {}\n\
+         Credits: {}",
+        name,
+        race.label(),
+        exp.level,
+        exp.xp,
+        next,
+        health.current,
+        health.max,
+        stamina,
+        attrs.strength,
+        attrs.agility,
+        attrs.intellect,
+        world.get::<Credits>(player).map_or(0, |c| c.0)
+    );
+    reply(world, player, text);
+}
+
+pub fn cmd_quests(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let Some(log) = world.get::<QuestLog>(player) else {
+        return;
+    };
+    let registry = world.resource::<QuestRegistry>();
+
+    let mut lines = Vec::new();
+    for entry in &log.active {
+        let Some(def) = registry.0.get(&entry.quest) else {
+            continue;
+        };
+        lines.push(format!("{}\n  {}", def.name, def.description));
+        for (obj, count) in def.objectives.iter().zip(&entry.progress) {
+            let mark = if *count >= obj.count { 'x' } else { ' ' };
+            lines.push(format!(
+                "  [{}] {} ({}/{})",
+                mark, obj.text, count, obj.count
+            ));
+        }
+    }
+
+    let mut text = if lines.is_empty() {
+        "You have no active quests.".to_string()
+    } else {
+        format!("Active quests:\n{}", lines.join("\n"))
+    };
+    if !log.completed.is_empty() {
+        text.push_str(&format!("\nCompleted quests: {}", log.completed.len()));
+    }
+    reply(world, player, text);
+}
+
+pub fn cmd_time(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let clock = world.resource::<GameClock>();
+    let text = format!(
+        "It is {:02}:{:02} on day {}, {}.",
+        clock.hour(),
+        clock.minute(),
+        clock.day(),
+        clock.time_of_day().label()
+    );
+    reply(world, player, text);
+}
+
+pub fn cmd_weather(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    let text = if !is_outdoors(world, room) {
+        "You can't see the sky from in here.".to_string()
+    } else {
+        let zone = world
+            .get::<Zone>(room)
+            .map_or(String::new(), |z| z.0.clone());
+        let weather = world.resource::<ZoneWeather>().get(&zone);
+        let time = world.resource::<GameClock>().time_of_day();
+        format!("{} It is {}.", weather.describe(), time.label())
+    };
+    reply(world, player, text);
+}
+
+pub fn cmd_who(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let mut rows: Vec<(String, &'static str, Entity)> = world
+        .query_filtered::<(Entity, &Name, &Race, &PlayerState, &Location), With<Player>>()
+        .iter(world)
+        .filter(|(ent, _, _, state, _)| {
+            **state == PlayerState::Active && can_see(world, player, *ent)
+        })
+        .map(|(_, name, race, _, loc)| (name.0.clone(), race.label(), loc.0))
+        .collect();
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let zones = world.resource::<ZoneRegistry>();
+    let lines: Vec<String> = rows
+        .iter()
+        .map(|(name, race, room)| {
+            let zone = world
+                .get::<Zone>(*room)
+                .and_then(|z| zones.id_to_name.get(&z.0))
+                .map_or("Unknown", String::as_str);
+            format!("  {:<16} {:<12} {}", name, race, zone)
+        })
+        .collect();
+
+    let text = format!("Players online ({}):\n{}", lines.len(), lines.join("\n"));
+    reply(world, player, text);
+}
@@ -0,0 +1,167 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::reply;
+use crate::core::components::{Corpse, HeldBy, Item, Location};
+use crate::core::events::{BroadcastRoomEvent, QuestEvent};
+use crate::core::quests::QuestTrigger;
+use crate::core::text::display_name;
+
+use bevy_ecs::prelude::*;
+
+/// Items lying loose in `room`
+pub fn items_in_room(world: &mut World, room: Entity) -> Vec<(Entity, Item)> {
+    world
+        .query::<(Entity, &Item, &Location)>()
+        .iter(world)
+        .filter(|(_, _, loc)| loc.0 == room)
+        .map(|(ent, item, _)| (ent, item.clone()))
+        .collect()
+}
+
+/// Items carried by, or stored inside, `holder`
+pub fn items_held_by(world: &mut World, holder: Entity) -> Vec<(Entity, Item)> {
+    world
+        .query::<(Entity, &Item, &HeldBy)>()
+        .iter(world)
+        .filter(|(_, _, held)| held.0 == holder)
+        .map(|(ent, item, _)| (ent, item.clone()))
+        .collect()
+}
+
+fn select(items: Vec<(Entity, Item)>, keyword: &str) -> Vec<(Entity, Item)> {
+    if keyword.eq_ignore_ascii_case("all") {
+        items
+    } else {
+        items
+            .into_iter()
+            .filter(|(_, item)| item.matches(keyword))
+            .take(1)
+            .collect()
+    }
+}
+
+pub fn cmd_drop(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    if args.is_empty() {
+        reply(world, player, "Drop what?".to_string());
+        return;
+    }
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+
+    let chosen = select(items_held_by(world, player), args[0]);
+    if chosen.is_empty() {
+        reply(world, player, "You aren't carrying that.".to_string());
+        return;
+    }
+
+    let name = display_name(world, player);
+    for (ent, item) in chosen {
+        world
+            .entity_mut(ent)
+            .remove::<HeldBy>()
+            .insert(Location(room));
+        reply(world, player, format!("You drop {}.", item.name));
+        world.write_message(BroadcastRoomEvent {
+            from: player,
+            room,
+            text: format!("{} drops {}.", name, item.name),
+            unseen: Some(format!("Someone drops {}.", item.name)),
+        });
+    }
+}
+
+pub fn cmd_get(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    if args.is_empty() {
+        reply(world, player, "Get what?".to_string());
+        return;
+    }
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+
+    let container = match args.get(1) {
+        Some(word) => match select(items_in_room(world, room), word).pop() {
+            Some(found) => Some(found),
+            None => {
+                reply(world, player, "You don't see that here.".to_string());
+                return;
+            }
+        },
+        None => None,
+    };
+
+    let candidates = match &container {
+        Some((ent, _)) => items_held_by(world, *ent),
+        None => items_in_room(world, room),
+    };
+    let selected = select(candidates, args[0]);
+    let any_selected = !selected.is_empty();
+    let chosen: Vec<(Entity, Item)> = selected
+        .into_iter()
+        .filter(|(ent, _)| world.get::<Corpse>(*ent).is_none())
+        .collect();
+    if chosen.is_empty() && any_selected {
+        reply(world, player, "You can't carry that.".to_string());
+        return;
+    }
+    if chosen.is_empty() {
+        let text = match &container {
+            Some((_, c)) => format!("There is nothing like that in {}.", c.name),
+            None => "You don't see that here.".to_string(),
+        };
+        reply(world, player, text);
+        return;
+    }
+
+    let name = display_name(world, player);
+    for (ent, item) in chosen {
+        world
+            .entity_mut(ent)
+            .remove::<Location>()
+            .insert(HeldBy(player));
+        let source = match &container {
+            Some((_, c)) => format!(" from {}", c.name),
+            None => String::new(),
+        };
+        reply(world, player, format!("You get {}{}.", item.name, source));
+        world.write_message(BroadcastRoomEvent {
+            from: player,
+            room,
+            text: format!("{} gets {}{}.", name, item.name, source),
+            unseen: Some(format!("Someone gets {}{}.", item.name, source)),
+        });
+        world.write_message(QuestEvent {
+            player,
+            trigger: QuestTrigger::Pickup,
+        });
+    }
+}
+
+pub fn cmd_inventory(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let items = items_held_by(world, player);
+    let text = if items.is_empty() {
+        "You are carrying nothing.".to_string()
+    } else {
+        let lines: Vec<String> = items
+            .iter()
+            .map(|(_, item)| format!("  {}", item.name))
+            .collect();
+        format!("You are carrying:\n{}", lines.join("\n"))
+    };
+    reply(world, player, text);
+}
@@ -0,0 +1,257 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::reply;
+use crate::core::components::{Admin, Location, Name};
+use crate::core::input::{EditTarget, open_editor, page};
+use crate::core::mail::{Board, Letter, Post, mail_recipient_exists};
+use crate::core::storage::{BoardStore, MailStore, validate_name};
+use crate::core::text::capitalize;
+
+use bevy_ecs::prelude::*;
+use tracing::error;
+
+/// Parses a 1-based message number into an index
+fn index(arg: Option<&&str>, len: usize) -> Option<usize> {
+    arg.and_then(|a| a.parse::<usize>().ok())
+        .filter(|n| (1..=len).contains(n))
+        .map(|n| n - 1)
+}
+
+fn subject_of(args: &[&str]) -> String {
+    if args.is_empty() {
+        "(no subject)".to_string()
+    } else {
+        args.join(" ")
+    }
+}
+
+fn format_letter(n: usize, letter: &Letter) -> String {
+    format!(
+        "Letter {} from {}\nSubject: {}\n\n{}",
+        n,
+        letter.from,
+        letter.subject,
+        letter.body.join("\n")
+    )
+}
+
+fn format_post(n: usize, post: &Post) -> String {
+    format!(
+        "Note {} by {}\nSubject: {}\n\n{}",
+        n,
+        post.author,
+        post.subject,
+        post.body.join("\n")
+    )
+}
+
+pub fn cmd_mail(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(name) = world.get::<Name>(player).map(|n| n.0.clone()) else {
+        return;
+    };
+    let sub = args.first().map(|a| a.to_lowercase());
+
+    if sub.as_deref() == Some("send") {
+        let Some(to) = args.get(1) else {
+            reply(
+                world,
+                player,
+                "Usage: mail send <player> [subject]".to_string(),
+            );
+            return;
+        };
+        if validate_name(to).is_err() || !mail_recipient_exists(world, to) {
+            reply(
+                world,
+                player,
+                format!("There is no one named {}.", capitalize(to)),
+            );
+            return;
+        }
+        let target = EditTarget::Mail {
+            to: to.to_lowercase(),
+            subject: subject_of(&args[2..]),
+        };
+        open_editor(world, player, target, Vec::new());
+        return;
+    }
+
+    let store = world.resource::<MailStore>();
+    let mut mailbox = match store.load(&name) {
+        Ok(m) => m,
+        Err(e) => {
+            error!("Failed to read mailbox for {}: {:?}", name, e);
+            reply(
+                world,
+                player,
+                "The mail system is unavailable right now.".to_string(),
+            );
+            return;
+        }
+    };
+
+    let text = match sub.as_deref() {
+        None | Some("list") => {
+            if mailbox.letters.is_empty() {
+                "Your mailbox is empty.".to_string()
+            } else {
+                let lines: Vec<String> = mailbox
+                    .letters
+                    .iter()
+                    .enumerate()
+                    .map(|(i, l)| {
+                        format!(
+                            "{:>3}. {}{}: {}",
+                            i + 1,
+                            if l.read { "" } else { "[new] " },
+                            l.from,
+                            l.subject
+                        )
+                    })
+                    .collect();
+                format!("Your mailbox:\n{}", lines.join("\n"))
+            }
+        }
+        Some("read") => match index(args.get(1), mailbox.letters.len()) {
+            Some(i) => {
+                mailbox.letters[i].read = true;
+                let text = format_letter(i + 1, &mailbox.letters[i]);
+                if let Err(e) = store.save(&name, &mailbox) {
+                    error!("Failed to save mailbox for {}: {:?}", name, e);
+                }
+                text
+            }
+            None => "Read which letter?".to_string(),
+        },
+        Some("delete") => match index(args.get(1), mailbox.letters.len()) {
+            Some(i) => {
+                let letter = mailbox.letters.remove(i);
+                match store.save(&name, &mailbox) {
+                    Ok(()) => format!("Deleted the letter from {}.", letter.from),
+                    Err(e) => {
+                        error!("Failed to save mailbox for {}: {:?}", name, e);
+                        "The mail system is unavailable right now.".to_string()
+                    }
+                }
+            }
+            None => "Delete which letter?".to_string(),
+        },
+        _ => "Usage: mail [list | read <n> | delete <n> | send <player> [subject]]".to_string(),
+    };
+    page(world, player, text);
+}
+
+/// Bulletin board in the player's room, telling them if there is none
+fn board_here(world: &mut World, player: Entity) -> Option<Board> {
+    let room = world.get::<Location>(player)?.0;
+    let found = world
+        .query::<(&Board, &Location)>()
+        .iter(world)
+        .find(|(_, loc)| loc.0 == room)
+        .map(|(board, _)| board.clone());
+    if found.is_none() {
+        reply(world, player, "There is no board here.".to_string());
+    }
+    found
+}
+
+pub fn cmd_read(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(board) = board_here(world, player) else {
+        return;
+    };
+    let posts = match world.resource::<BoardStore>().load(&board.id) {
+        Ok(p) => p.posts,
+        Err(e) => {
+            error!("Failed to read board {}: {:?}", board.id, e);
+            reply(
+                world,
+                player,
+                "The board is unreadable right now.".to_string(),
+            );
+            return;
+        }
+    };
+
+    let text = if args.is_empty() {
+        if posts.is_empty() {
+            format!("Nothing is pinned to {}.", board.name)
+        } else {
+            let lines: Vec<String> = posts
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("{:>3}. {}: {}", i + 1, p.author, p.subject))
+                .collect();
+            format!("{}:\n{}", capitalize(&board.name), lines.join("\n"))
+        }
+    } else {
+        match index(args.first(), posts.len()) {
+            Some(i) => format_post(i + 1, &posts[i]),
+            None => "Read which note?".to_string(),
+        }
+    };
+    page(world, player, text);
+}
+
+pub fn cmd_post(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(board) = board_here(world, player) else {
+        return;
+    };
+    let target = EditTarget::Post {
+        board: board.id,
+        subject: subject_of(args),
+    };
+    open_editor(world, player, target, Vec::new());
+}
+
+pub fn cmd_remove(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(name) = world.get::<Name>(player).map(|n| n.0.clone()) else {
+        return;
+    };
+    let Some(board) = board_here(world, player) else {
+        return;
+    };
+    let admin = world.get::<Admin>(player).is_some();
+    let store = world.resource::<BoardStore>();
+    let mut posts = match store.load(&board.id) {
+        Ok(p) => p,
+        Err(e) => {
+            error!("Failed to read board {}: {:?}", board.id, e);
+            reply(
+                world,
+                player,
+                "The board is unreadable right now.".to_string(),
+            );
+            return;
+        }
+    };
+
+    let text = match index(args.first(), posts.posts.len()) {
+        Some(i) if admin || posts.posts[i].author.eq_ignore_ascii_case(&name) => {
+            let post = posts.posts.remove(i);
+            match store.save(&board.id, &posts) {
+                Ok(()) => format!("You take down \"{}\".", post.subject),
+                Err(e) => {
+                    error!("Failed to save board {}: {:?}", board.id, e);
+                    "The note won't come loose.".to_string()
+                }
+            }
+        }
+        Some(_) => "You can only remove your own notes.".to_string(),
+        None => "Remove which note?".to_string(),
+    };
+    reply(world, player, text);
+}
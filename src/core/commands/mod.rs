@@ -14,22 +14,48 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod abilities;
+pub mod channels;
+pub mod combat;
+pub mod group;
+pub mod info;
+pub mod items;
+pub mod mail;
+pub mod moderation;
 pub mod movement;
+pub mod olc;
+pub mod shop;
 pub mod social;
+pub mod stealth;
 
 use std::collections::HashMap;
 
+use crate::core::components::{Location, Muted, Name, Player, PlayerState, Race, Stamina};
 use crate::core::events::OutputEvent;
+use crate::core::input::page;
+use crate::core::race::RaceTrait;
+use crate::core::socials;
+use crate::core::util::now_secs;
+use crate::core::visibility::can_see;
 
 use bevy_ecs::prelude::*;
 
 pub type CommandHandler =
     fn(Entity, &mut World, /* full input */ &str, /* args */ &[&str]);
 
+/// Sends one line of output to `player`
+pub fn reply(world: &mut World, player: Entity, text: String) {
+    world.write_message(OutputEvent { player, text });
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum CommandScope {
     Any,
     Active,
+    /// Active players carrying the `Admin` marker.
+    Admin,
+    /// Active players carrying the `Builder` or `Admin` marker.
+    Builder,
 }
 
 struct CommandMetadata {
@@ -41,6 +67,83 @@ struct CommandMetadata {
 }
 
 const COMMAND_LIST: &[CommandMetadata] = &[
+    CommandMetadata {
+        name: "admin",
+        handler: Some(channels::cmd_admin),
+        description: "Talk on the admin channel, or 'admin history'",
+        aliases: &[],
+        scope: CommandScope::Admin,
+    },
+    CommandMetadata {
+        name: "affects",
+        handler: Some(info::cmd_affects),
+        description: "List the effects currently on you",
+        aliases: &["aff"],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "buy",
+        handler: Some(shop::cmd_buy),
+        description: "Buy an item from a shopkeeper",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "chanban",
+        handler: Some(channels::cmd_chanban),
+        description: "Ban or unban a player from a channel (admin)",
+        aliases: &[],
+        scope: CommandScope::Admin,
+    },
+    CommandMetadata {
+        name: "chanmute",
+        handler: Some(channels::cmd_chanmute),
+        description: "Mute or unmute a player on a channel (admin)",
+        aliases: &[],
+        scope: CommandScope::Admin,
+    },
+    CommandMetadata {
+        name: "channels",
+        handler: Some(channels::cmd_channels),
+        description: "List the channels you can use",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "clan",
+        handler: Some(channels::cmd_clan),
+        description: "Talk on the Predator clan channel",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "cloak",
+        handler: Some(abilities::cmd_cloak),
+        description: "Toggle your cloaking device (Predator)",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "close",
+        handler: Some(movement::cmd_close),
+        description: "Close a door",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "dig",
+        handler: Some(olc::cmd_dig),
+        description: "Dig a new room in a direction (builder)",
+        aliases: &[],
+        scope: CommandScope::Builder,
+    },
+    CommandMetadata {
+        name: "drop",
+        handler: Some(items::cmd_drop),
+        description: "Drop an item you are carrying",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
     CommandMetadata {
         name: "east",
         handler: Some(movement::cmd_east),
@@ -48,6 +151,55 @@ const COMMAND_LIST: &[CommandMetadata] = &[
         aliases: &["e"],
         scope: CommandScope::Active,
     },
+    CommandMetadata {
+        name: "emote",
+        handler: Some(social::cmd_emote),
+        description: "Describe an action to others in the room",
+        aliases: &["em"],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "flare",
+        handler: Some(abilities::cmd_flare),
+        description: "Light a flare that exposes cloaked hunters (Human)",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "follow",
+        handler: Some(group::cmd_follow),
+        description: "Follow another player as they move, or stop following",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "freeze",
+        handler: Some(moderation::cmd_freeze),
+        description: "Freeze or thaw a player (admin)",
+        aliases: &[],
+        scope: CommandScope::Admin,
+    },
+    CommandMetadata {
+        name: "get",
+        handler: Some(items::cmd_get),
+        description: "Pick up an item, optionally from a container",
+        aliases: &["take"],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "group",
+        handler: Some(group::cmd_group),
+        description: "Show your group, or invite, accept, leave, kick and set loot rules",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "gtell",
+        handler: Some(group::cmd_gtell),
+        description: "Talk to the members of your group",
+        aliases: &["gt"],
+        scope: CommandScope::Active,
+    },
     CommandMetadata {
         name: "help",
         handler: Some(cmd_help),
@@ -55,6 +207,69 @@ const COMMAND_LIST: &[CommandMetadata] = &[
         aliases: &[],
         scope: CommandScope::Any,
     },
+    CommandMetadata {
+        name: "hide",
+        handler: Some(stealth::cmd_hide),
+        description: "Hide out of sight until found or you give yourself away",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "hiss",
+        handler: Some(abilities::cmd_hiss),
+        description: "Terrify those around you (Xenomorph)",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "hive",
+        handler: Some(channels::cmd_hive),
+        description: "Commune with the Xenomorph hive-mind",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "ignore",
+        handler: Some(social::cmd_ignore),
+        description: "Ignore or unignore a player, or list who you ignore",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "inventory",
+        handler: Some(items::cmd_inventory),
+        description: "List what you are carrying",
+        aliases: &["i", "inv"],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "join",
+        handler: Some(channels::cmd_join),
+        description: "Join a chat channel",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "kill",
+        handler: Some(combat::cmd_kill),
+        description: "Attack someone in the room",
+        aliases: &["attack", "k"],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "leave",
+        handler: Some(channels::cmd_leave),
+        description: "Leave a chat channel",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "list",
+        handler: Some(shop::cmd_list),
+        description: "See what a shopkeeper has for sale",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
     CommandMetadata {
         name: "look",
         handler: Some(movement::cmd_look),
@@ -62,6 +277,41 @@ const COMMAND_LIST: &[CommandMetadata] = &[
         aliases: &["l"],
         scope: CommandScope::Any,
     },
+    CommandMetadata {
+        name: "mail",
+        handler: Some(mail::cmd_mail),
+        description: "List, read, delete or send mail",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "map",
+        handler: Some(movement::cmd_map),
+        description: "Show a map of the rooms around you",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "medkit",
+        handler: Some(abilities::cmd_medkit),
+        description: "Patch yourself up over time (Human)",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "mute",
+        handler: Some(moderation::cmd_mute),
+        description: "Mute a player for some minutes, or 'mute <player> off' (admin)",
+        aliases: &[],
+        scope: CommandScope::Admin,
+    },
+    CommandMetadata {
+        name: "newbie",
+        handler: Some(channels::cmd_newbie),
+        description: "Ask questions on the newbie channel",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
     CommandMetadata {
         name: "north",
         handler: Some(movement::cmd_north),
@@ -69,6 +319,48 @@ const COMMAND_LIST: &[CommandMetadata] = &[
         aliases: &["n"],
         scope: CommandScope::Active,
     },
+    CommandMetadata {
+        name: "open",
+        handler: Some(movement::cmd_open),
+        description: "Open a door",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "ooc",
+        handler: Some(channels::cmd_ooc),
+        description: "Talk out of character, or 'ooc history'",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "path",
+        handler: Some(movement::cmd_path),
+        description: "Show directions to a room or landmark",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "plasma",
+        handler: Some(abilities::cmd_plasma),
+        description: "Fire your plasma caster at a target (Predator)",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "post",
+        handler: Some(mail::cmd_post),
+        description: "Pin a note to the bulletin board here",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "quests",
+        handler: Some(info::cmd_quests),
+        description: "Show your quest journal",
+        aliases: &["journal"],
+        scope: CommandScope::Active,
+    },
     CommandMetadata {
         name: "quit",
         handler: None,
@@ -76,6 +368,55 @@ const COMMAND_LIST: &[CommandMetadata] = &[
         aliases: &[],
         scope: CommandScope::Any,
     },
+    CommandMetadata {
+        name: "rdelete",
+        handler: Some(olc::cmd_rdelete),
+        description: "Delete the room you're standing in (builder)",
+        aliases: &[],
+        scope: CommandScope::Builder,
+    },
+    CommandMetadata {
+        name: "read",
+        handler: Some(mail::cmd_read),
+        description: "Read the bulletin board here, or one of its notes",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "redit",
+        handler: Some(olc::cmd_redit),
+        description: "Edit this room's name, description or exits (builder)",
+        aliases: &[],
+        scope: CommandScope::Builder,
+    },
+    CommandMetadata {
+        name: "reply",
+        handler: Some(social::cmd_reply),
+        description: "Reply to the last player who sent you a tell",
+        aliases: &["r"],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "remove",
+        handler: Some(mail::cmd_remove),
+        description: "Take one of your notes down from the board",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "report",
+        handler: Some(moderation::cmd_report),
+        description: "Report a player to the moderators, with what you've recently seen",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "reports",
+        handler: Some(moderation::cmd_reports),
+        description: "Review recent player reports (admin)",
+        aliases: &[],
+        scope: CommandScope::Admin,
+    },
     CommandMetadata {
         name: "say",
         handler: Some(social::cmd_say),
@@ -83,6 +424,27 @@ const COMMAND_LIST: &[CommandMetadata] = &[
         aliases: &[],
         scope: CommandScope::Any,
     },
+    CommandMetadata {
+        name: "score",
+        handler: Some(info::cmd_score),
+        description: "Show your character sheet",
+        aliases: &["sc"],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "search",
+        handler: Some(stealth::cmd_search),
+        description: "Search the room for hidden people and exits",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "sell",
+        handler: Some(shop::cmd_sell),
+        description: "Sell an item to a shopkeeper",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
     CommandMetadata {
         name: "shout",
         handler: Some(social::cmd_shout),
@@ -90,6 +452,13 @@ const COMMAND_LIST: &[CommandMetadata] = &[
         aliases: &[],
         scope: CommandScope::Any,
     },
+    CommandMetadata {
+        name: "sneak",
+        handler: Some(stealth::cmd_sneak),
+        description: "Toggle moving quietly past onlookers",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
     CommandMetadata {
         name: "south",
         handler: Some(movement::cmd_south),
@@ -97,6 +466,55 @@ const COMMAND_LIST: &[CommandMetadata] = &[
         aliases: &["s"],
         scope: CommandScope::Active,
     },
+    CommandMetadata {
+        name: "talk",
+        handler: Some(social::cmd_talk),
+        description: "Talk to someone, or answer with talk <number>",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "tell",
+        handler: Some(social::cmd_tell),
+        description: "Send a private message to a player",
+        aliases: &["t"],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "time",
+        handler: Some(info::cmd_time),
+        description: "Show the time of day",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "transactions",
+        handler: Some(shop::cmd_transactions),
+        description: "Review recent shop transactions (admin)",
+        aliases: &[],
+        scope: CommandScope::Admin,
+    },
+    CommandMetadata {
+        name: "travel",
+        handler: Some(movement::cmd_travel),
+        description: "Walk to a landmark, or 'travel stop'",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "value",
+        handler: Some(shop::cmd_value),
+        description: "Ask what a shopkeeper would pay for an item",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
+    CommandMetadata {
+        name: "weather",
+        handler: Some(info::cmd_weather),
+        description: "Look at the sky",
+        aliases: &[],
+        scope: CommandScope::Active,
+    },
     CommandMetadata {
         name: "west",
         handler: Some(movement::cmd_west),
@@ -111,6 +529,27 @@ const COMMAND_LIST: &[CommandMetadata] = &[
         aliases: &[],
         scope: CommandScope::Any,
     },
+    CommandMetadata {
+        name: "who",
+        handler: Some(info::cmd_who),
+        description: "List the players online",
+        aliases: &[],
+        scope: CommandScope::Any,
+    },
+    CommandMetadata {
+        name: "zcreate",
+        handler: Some(olc::cmd_zcreate),
+        description: "Create a new zone (builder)",
+        aliases: &[],
+        scope: CommandScope::Builder,
+    },
+    CommandMetadata {
+        name: "zsave",
+        handler: Some(olc::cmd_zsave),
+        description: "Save a zone, or 'zsave all' dirty zones (builder)",
+        aliases: &[],
+        scope: CommandScope::Builder,
+    },
 ];
 
 #[derive(Resource)]
@@ -159,13 +598,108 @@ impl CommandMap {
             scopes,
         }
     }
+
+    /// Registers each social as a command, leaving built-in commands of the same name alone
+    pub fn register_socials(&mut self, names: &[String]) {
+        let mut added: Vec<&str> = Vec::new();
+        for name in names {
+            if self.handlers.contains_key(name) {
+                continue;
+            }
+            self.handlers.insert(name.clone(), socials::cmd_social);
+            self.scopes.insert(name.clone(), CommandScope::Active);
+            added.push(name);
+        }
+        if !added.is_empty() {
+            added.sort();
+            self.help_text
+                .push_str(&format!("\nSocials: {}", added.join(", ")));
+        }
+    }
 }
 
 fn cmd_help(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
-    if let Some(command_map) = world.get_resource::<CommandMap>() {
-        world.write_message(OutputEvent {
-            player,
-            text: command_map.help_text.clone(),
-        });
+    if let Some(text) = world
+        .get_resource::<CommandMap>()
+        .map(|map| map.help_text.clone())
+    {
+        page(world, player, text);
+    }
+}
+
+/// Finds another named entity in `room`, visible to `viewer`, whose name starts with `target`
+pub fn find_in_room(
+    world: &mut World,
+    room: Entity,
+    target: &str,
+    viewer: Entity,
+) -> Option<Entity> {
+    let target = target.to_lowercase();
+    let candidates: Vec<Entity> = world
+        .query::<(Entity, &Name, &Location)>()
+        .iter(world)
+        .filter(|(ent, name, loc)| {
+            *ent != viewer
+                && loc.0 == room
+                && name
+                    .0
+                    .to_lowercase()
+                    .split_whitespace()
+                    .any(|word| word.starts_with(&target))
+        })
+        .map(|(ent, _, _)| ent)
+        .collect();
+    candidates
+        .into_iter()
+        .find(|ent| can_see(world, viewer, *ent))
+}
+
+/// Finds an active player anywhere in the game by full name, case-insensitively
+pub fn find_player(world: &mut World, name: &str) -> Option<Entity> {
+    world
+        .query_filtered::<(Entity, &Name, &PlayerState), With<Player>>()
+        .iter(world)
+        .find(|(_, n, state)| **state == PlayerState::Active && n.0.eq_ignore_ascii_case(name))
+        .map(|(ent, _, _)| ent)
+}
+
+/// Deducts stamina for an exertion, telling the player when they are too exhausted
+pub fn spend_stamina(world: &mut World, player: Entity, cost: u32) -> bool {
+    if world
+        .get::<Race>(player)
+        .is_some_and(|r| r.has_trait(RaceTrait::FatigueImmune))
+    {
+        return true;
+    }
+    if let Some(mut stamina) = world.get_mut::<Stamina>(player) {
+        if stamina.current < cost {
+            reply(world, player, "You are too exhausted.".to_string());
+            return false;
+        }
+        stamina.current -= cost;
+    }
+    true
+}
+
+/// Refuses speech from a muted player, lifting the mute once it has expired
+pub fn is_muted(world: &mut World, player: Entity) -> bool {
+    let Some(muted) = world.get::<Muted>(player).copied() else {
+        return false;
+    };
+    let now = now_secs();
+    if now >= muted.until {
+        world.entity_mut(player).remove::<Muted>();
+        return false;
     }
+    let minutes = (muted.until - now).div_ceil(60);
+    reply(
+        world,
+        player,
+        format!(
+            "You have been muted by a moderator for another {} minute{}.",
+            minutes,
+            if minutes == 1 { "" } else { "s" }
+        ),
+    );
+    true
 }
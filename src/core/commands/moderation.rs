@@ -0,0 +1,177 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::{find_player, reply};
+use crate::core::components::{Admin, Frozen, Muted, Scrollback};
+use crate::core::events::SaveEvent;
+use crate::core::input::InputModes;
+use crate::core::moderation::{REPORT_EVIDENCE_LINES, ReportLog};
+use crate::core::text::{capitalize, display_name};
+use crate::core::util::now_secs;
+
+use bevy_ecs::prelude::*;
+
+/// Online player named by the first argument, telling the moderator if there is none
+/// One year; longer mutes are cut down to this.
+const MAX_MUTE_MINUTES: u64 = 365 * 24 * 60;
+
+fn target(world: &mut World, player: Entity, args: &[&str]) -> Option<Entity> {
+    let name = args.first()?;
+    let found = find_player(world, name);
+    if found.is_none() {
+        reply(
+            world,
+            player,
+            format!("{} is not online.", capitalize(name)),
+        );
+    }
+    found
+}
+
+pub fn cmd_mute(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let usage = "Usage: mute <player> <minutes> | mute <player> off";
+    let Some(amount) = args.get(1) else {
+        reply(world, player, usage.to_string());
+        return;
+    };
+    let minutes = if amount.eq_ignore_ascii_case("off") {
+        None
+    } else {
+        match amount.parse::<u64>() {
+            Ok(m) if m > 0 => Some(m.min(MAX_MUTE_MINUTES)),
+            _ => {
+                reply(world, player, usage.to_string());
+                return;
+            }
+        }
+    };
+    let Some(victim) = target(world, player, args) else {
+        return;
+    };
+    let name = display_name(world, victim);
+
+    let (mine, theirs) = match minutes {
+        Some(m) => {
+            world.entity_mut(victim).insert(Muted {
+                until: now_secs().saturating_add(m.saturating_mul(60)),
+            });
+            (
+                format!("{} is muted for {} minutes.", name, m),
+                format!("You have been muted by a moderator for {} minutes.", m),
+            )
+        }
+        None => {
+            world.entity_mut(victim).remove::<Muted>();
+            (
+                format!("{} is no longer muted.", name),
+                "You are no longer muted.".to_string(),
+            )
+        }
+    };
+    reply(world, player, mine);
+    reply(world, victim, theirs);
+    world.write_message(SaveEvent { player: victim });
+}
+
+pub fn cmd_freeze(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    if args.is_empty() {
+        reply(world, player, "Freeze whom?".to_string());
+        return;
+    }
+    let Some(victim) = target(world, player, args) else {
+        return;
+    };
+    if victim == player {
+        reply(world, player, "You can't freeze yourself.".to_string());
+        return;
+    }
+    let name = display_name(world, victim);
+
+    let (mine, theirs) = if world.get::<Frozen>(victim).is_some() {
+        world.entity_mut(victim).remove::<Frozen>();
+        (
+            format!("{} thaws out.", name),
+            "You can move again.".to_string(),
+        )
+    } else {
+        // Drop any open editor or pager so their lines can't slip past the freeze.
+        world
+            .entity_mut(victim)
+            .insert((Frozen, InputModes::default()));
+        (
+            format!("{} is frozen solid.", name),
+            "A moderator has frozen you in place.".to_string(),
+        )
+    };
+    reply(world, player, mine);
+    reply(world, victim, theirs);
+    world.write_message(SaveEvent { player: victim });
+}
+
+pub fn cmd_report(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    if args.len() < 2 {
+        reply(world, player, "Usage: report <player> <reason>".to_string());
+        return;
+    }
+    let reporter = display_name(world, player);
+    let accused = capitalize(&args[0].to_lowercase());
+    let reason = args[1..].join(" ");
+    let evidence: Vec<String> = world.get::<Scrollback>(player).map_or(Vec::new(), |s| {
+        let skip = s.0.len().saturating_sub(REPORT_EVIDENCE_LINES);
+        s.0.iter().skip(skip).cloned().collect()
+    });
+
+    world
+        .resource_mut::<ReportLog>()
+        .record(&reporter, &accused, &reason, &evidence);
+
+    let admins: Vec<Entity> = world
+        .query_filtered::<Entity, With<Admin>>()
+        .iter(world)
+        .filter(|a| *a != player)
+        .collect();
+    for admin in admins {
+        reply(
+            world,
+            admin,
+            format!("[Report] {} reported {}: {}", reporter, accused, reason),
+        );
+    }
+    reply(
+        world,
+        player,
+        format!(
+            "Your report about {} has been filed with the last {} lines you saw.",
+            accused,
+            evidence.len()
+        ),
+    );
+}
+
+pub fn cmd_reports(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let count = args
+        .first()
+        .and_then(|a| a.parse::<usize>().ok())
+        .unwrap_or(10);
+    let log = world.resource::<ReportLog>();
+    let lines: Vec<String> = log.recent.iter().rev().take(count).rev().cloned().collect();
+    let text = if lines.is_empty() {
+        "No reports have been filed.".to_string()
+    } else {
+        format!("Recent reports:\n{}", lines.join("\n"))
+    };
+    reply(world, player, text);
+}
@@ -14,12 +14,39 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::core::components::{Location, Name, Room, Zone};
-use crate::core::events::{BroadcastRoomEvent, OutputEvent};
-use crate::core::world::ZoneRegistry;
+use crate::core::clock::room_description;
+use crate::core::combat::Fighting;
+use crate::core::commands::items::{items_held_by, items_in_room};
+use crate::core::commands::{find_in_room, reply, spend_stamina};
+use crate::core::components::{
+    Cloaked, Doors, Extras, Frozen, Health, Location, Name, Player, Race, Room, Sneaking,
+    VisitedRooms, Zone,
+};
+use crate::core::environment::spring_death_trap;
+use crate::core::events::{BroadcastRoomEvent, ExperienceEvent, QuestEvent};
+use crate::core::groups::Following;
+use crate::core::mail::Board;
+use crate::core::mapping::{MAP_RADIUS, minimap};
+use crate::core::npc::Npc;
+use crate::core::pathing::{
+    TRAVEL_STEP_TICKS, Travelling, concealed, describe_route, find_landmark, find_path, landmarks,
+    passable,
+};
+use crate::core::progression::EXPLORE_XP;
+use crate::core::quests::QuestTrigger;
+use crate::core::race::RaceTrait;
+use crate::core::scripting::{TriggerKind, fire};
+use crate::core::stealth::{notify_observers, reveal};
+use crate::core::text::{capitalize, display_name};
+use crate::core::visibility::{can_see, can_see_room};
+use crate::core::world::{GameTick, RoomRegistry, ZoneRegistry, qualify};
 
 use bevy_ecs::prelude::*;
 
+const MOVE_STAMINA: u32 = 1;
+
+const DARKNESS: &str = "It is pitch black. You can't make out a thing.";
+
 #[derive(Copy, Clone)]
 pub enum StdExits {
     North,
@@ -29,6 +56,16 @@ pub enum StdExits {
 }
 
 impl StdExits {
+    pub fn parse(dir: &str) -> Option<StdExits> {
+        match dir {
+            "north" | "n" => Some(StdExits::North),
+            "south" | "s" => Some(StdExits::South),
+            "east" | "e" => Some(StdExits::East),
+            "west" | "w" => Some(StdExits::West),
+            _ => None,
+        }
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             StdExits::North => "north",
@@ -57,34 +94,252 @@ impl StdExits {
     }
 }
 
+pub fn cmd_close(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    set_door(player, world, args, true);
+}
+
 pub fn cmd_east(player: Entity, world: &mut World, full: &str, args: &[&str]) {
     cmd_move(StdExits::East, player, world, full, args);
 }
 
-pub fn cmd_look(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
-    if let Some(loc) = world.get::<Location>(player) {
-        if let Some(room) = world.get::<Room>(loc.0) {
-            let text = format!("{}\n\n{}", room.name, room.description);
-            world.write_message(OutputEvent { player, text });
-            return;
+pub fn cmd_look(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(loc) = world.get::<Location>(player).map(|l| l.0) else {
+        reply(
+            world,
+            player,
+            "You are nowhere. (no location set)".to_string(),
+        );
+        return;
+    };
+    let args = match args {
+        ["at", rest @ ..] => rest,
+        _ => args,
+    };
+    let text = match args.first() {
+        None => room_view(world, player, loc),
+        Some(word) => match StdExits::parse(&word.to_lowercase()) {
+            Some(dir) => peek(world, player, loc, dir),
+            None => look_at(world, player, loc, word),
+        },
+    };
+    reply(world, player, text);
+}
+
+/// The full description of a room as seen by `viewer`
+fn room_view(world: &mut World, viewer: Entity, loc: Entity) -> String {
+    let Some(room) = world.get::<Room>(loc) else {
+        return "You are in an unknown location.".to_string();
+    };
+    let mut exits: Vec<String> = room
+        .exits
+        .keys()
+        .filter(|dir| !concealed(world, loc, dir))
+        .cloned()
+        .collect();
+    exits.sort();
+    let lit = can_see_room(world, viewer, loc);
+    let mut text = if lit {
+        let description = room_description(world, loc, &room.description);
+        format!("{}\n\n{}", room.name, description)
+    } else {
+        format!("{}\n\n{}", room.name, DARKNESS)
+    };
+    describe_contents(world, viewer, loc, lit, &mut text);
+
+    let exits: Vec<String> = exits
+        .into_iter()
+        .map(|dir| {
+            if passable(world, loc, &dir) {
+                dir
+            } else {
+                format!("{} (closed)", dir)
+            }
+        })
+        .collect();
+    if exits.is_empty() {
+        text.push_str("\n\nExits: none.");
+    } else {
+        text.push_str(&format!("\n\nExits: {}.", exits.join(", ")));
+    }
+    text
+}
+
+/// Appends whatever `viewer` can make out in a room; in the dark that is at most who's there
+fn describe_contents(world: &mut World, viewer: Entity, loc: Entity, lit: bool, text: &mut String) {
+    if lit {
+        for (_, item) in items_in_room(world, loc) {
+            text.push('\n');
+            text.push_str(&item.description);
+        }
+    }
+    let npcs: Vec<(Entity, String, String)> = world
+        .query::<(Entity, &Npc, &Name, &Location)>()
+        .iter(world)
+        .filter(|(_, _, _, npc_loc)| npc_loc.0 == loc)
+        .map(|(ent, npc, name, _)| (ent, npc.description.clone(), name.0.clone()))
+        .collect();
+    for (ent, description, name) in npcs {
+        if !can_see(world, viewer, ent) {
+            continue;
+        }
+        text.push('\n');
+        if lit {
+            text.push_str(&description);
+        } else {
+            text.push_str(&format!("{} is here.", capitalize(&name)));
         }
     }
+    if lit {
+        let boards: Vec<String> = world
+            .query::<(&Board, &Location)>()
+            .iter(world)
+            .filter(|(_, board_loc)| board_loc.0 == loc)
+            .map(|(board, _)| board.description.clone())
+            .collect();
+        for description in boards {
+            text.push('\n');
+            text.push_str(&description);
+        }
+    }
+    for name in players_in(world, loc, viewer) {
+        text.push_str(&format!("\n{} is here.", name));
+    }
+}
 
-    let text = "You are nowhere. (no location set)";
-    world.write_message(OutputEvent {
-        player,
-        text: text.to_string(),
-    });
+/// Names of the other players in `room` that `viewer` can see, sorted
+fn players_in(world: &mut World, room: Entity, viewer: Entity) -> Vec<String> {
+    let mut names: Vec<String> = world
+        .query_filtered::<(Entity, &Name, &Location), With<Player>>()
+        .iter(world)
+        .filter(|(ent, _, loc)| *ent != viewer && loc.0 == room)
+        .filter(|(ent, _, _)| can_see(world, viewer, *ent))
+        .map(|(_, name, _)| name.0.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Glances through an exit at the room beyond
+fn peek(world: &mut World, player: Entity, loc: Entity, dir: StdExits) -> String {
+    let Some(target) = world
+        .get::<Room>(loc)
+        .and_then(|r| r.exits.get(dir.as_str()).copied())
+        .filter(|_| !concealed(world, loc, dir.as_str()))
+    else {
+        return format!("You see nothing special to {}.", dir.as_str_noun());
+    };
+    if !passable(world, loc, dir.as_str()) {
+        return format!("The door to {} is closed.", dir.as_str_noun());
+    }
+    let Some(name) = world.get::<Room>(target).map(|r| r.name.clone()) else {
+        return format!("You see nothing special to {}.", dir.as_str_noun());
+    };
+    if !can_see_room(world, player, target) {
+        return format!("To {} lies only darkness.", dir.as_str_noun());
+    }
+
+    let mut seen: Vec<String> = world
+        .query_filtered::<(Entity, &Name, &Location), Or<(With<Npc>, With<Player>)>>()
+        .iter(world)
+        .filter(|(ent, _, l)| *ent != player && l.0 == target)
+        .filter(|(ent, _, _)| can_see(world, player, *ent))
+        .map(|(_, n, _)| n.0.clone())
+        .collect();
+    seen.sort();
+    let mut text = format!("To {} you see {}.", dir.as_str_noun(), name);
+    if !seen.is_empty() {
+        text.push_str(&format!(
+            "\n{} {} there.",
+            capitalize(&seen.join(", ")),
+            plural_be(seen.len())
+        ));
+    }
+    text
+}
+
+fn plural_be(count: usize) -> &'static str {
+    if count == 1 { "is" } else { "are" }
+}
+
+/// Describes scenery, an item, an NPC or a player matching `word`
+fn look_at(world: &mut World, player: Entity, loc: Entity, word: &str) -> String {
+    let lit = can_see_room(world, player, loc);
+    if lit
+        && let Some(extra) = world
+            .get::<Extras>(loc)
+            .and_then(|e| e.0.iter().find(|x| x.matches(word)))
+    {
+        return extra.description.clone();
+    }
+    if lit {
+        let carried = items_held_by(world, player);
+        if let Some((_, item)) = items_in_room(world, loc)
+            .into_iter()
+            .chain(carried)
+            .find(|(_, item)| item.matches(word))
+        {
+            return format!("{}\n{}", capitalize(&item.name), item.description);
+        }
+    }
+    let Some(target) = find_in_room(world, loc, word, player) else {
+        return if lit {
+            format!("You don't see '{}' here.", word)
+        } else {
+            "It is too dark to make anything out.".to_string()
+        };
+    };
+
+    let name = world
+        .get::<Name>(target)
+        .map_or(String::new(), |n| n.0.clone());
+    let mut text = match (world.get::<Npc>(target), world.get::<Race>(target)) {
+        (Some(npc), _) => npc.description.clone(),
+        (None, Some(race)) => format!("{} is a {}.", name, race.label()),
+        (None, None) => capitalize(&name),
+    };
+    if let Some(health) = world.get::<Health>(target) {
+        text.push_str(&format!("\n{} {}.", capitalize(&name), condition(health)));
+    }
+    text
+}
+
+/// A rough reading of how hurt someone looks
+fn condition(health: &Health) -> &'static str {
+    match health.current * 100 / health.max.max(1) {
+        100.. => "is in perfect health",
+        75..=99 => "has a few scratches",
+        50..=74 => "is wounded",
+        25..=49 => "is badly wounded",
+        _ => "is close to death",
+    }
+}
+
+pub fn cmd_map(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let Some(loc) = world.get::<Location>(player).map(|l| l.0) else {
+        reply(
+            world,
+            player,
+            "You are nowhere. (no location set)".to_string(),
+        );
+        return;
+    };
+    let name = world
+        .get::<Room>(loc)
+        .map(|r| r.name.clone())
+        .unwrap_or_default();
+    let text = format!("{}\n\n{}\n\n[@] you", name, minimap(world, loc, MAP_RADIUS));
+    reply(world, player, text);
 }
 
 pub fn cmd_move(dir: StdExits, player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
     let loc = match world.get::<Location>(player) {
         Some(l) => l.0,
         None => {
-            world.write_message(OutputEvent {
+            reply(
+                world,
                 player,
-                text: "You are nowhere. (no location set)".to_string(),
-            });
+                "You are nowhere. (no location set)".to_string(),
+            );
             return;
         }
     };
@@ -92,83 +347,354 @@ pub fn cmd_move(dir: StdExits, player: Entity, world: &mut World, _full: &str, _
     let room = match world.get_mut::<Room>(loc) {
         Some(r) => r,
         None => {
-            world.write_message(OutputEvent {
-                player,
-                text: "You are in an unknown location.".to_string(),
-            });
+            reply(world, player, "You are in an unknown location.".to_string());
             return;
         }
     };
 
     let dir_key = dir.as_str();
-    if let Some(&target_ent) = room.exits.get(dir_key) {
+    if let Some(&target_ent) = room.exits.get(dir_key)
+        && !concealed(world, loc, dir_key)
+    {
+        if !passable(world, loc, dir_key) {
+            reply(
+                world,
+                player,
+                format!("The door to {} is closed.", dir.as_str_noun()),
+            );
+            return;
+        }
+        if !spend_stamina(world, player, MOVE_STAMINA) {
+            return;
+        }
+        reveal(world, player);
         if let Some(mut player_loc) = world.get_mut::<Location>(player) {
             player_loc.0 = target_ent;
         }
 
-        let name = match world.get::<Name>(player) {
-            Some(n) => n.0.clone(),
-            None => "Someone".to_string(),
+        let name = display_name(world, player);
+        let stealthy = world
+            .get::<Race>(player)
+            .is_some_and(|r| r.has_trait(RaceTrait::Stealth));
+
+        let cloaked = world.get::<Cloaked>(player).is_some();
+        let sneaking = !cloaked && world.get::<Sneaking>(player).is_some();
+        let (leave_text, arrive_text) = if cloaked {
+            (
+                "The air ripples faintly.".to_string(),
+                "The air ripples faintly.".to_string(),
+            )
+        } else if sneaking && !stealthy {
+            (
+                format!("You notice {} sneaking off {}.", name, dir_key),
+                format!(
+                    "You notice {} sneaking in from {}.",
+                    name,
+                    dir.opposite().as_str_noun()
+                ),
+            )
+        } else if stealthy {
+            (
+                format!("Something skitters away {}.", dir_key),
+                format!(
+                    "Something skitters in from {}.",
+                    dir.opposite().as_str_noun()
+                ),
+            )
+        } else {
+            (
+                format!("{} leaves {}.", name, dir_key),
+                format!("{} arrives from {}.", name, dir.opposite().as_str_noun()),
+            )
         };
 
         if world.get::<Room>(target_ent).is_some() {
             let text = format!("You go {}.", dir_key);
-            world.write_message(OutputEvent { player, text });
-            world.write_message(BroadcastRoomEvent {
-                from: player,
-                room: loc,
-                text: format!("{} leaves {}.", name, dir_key),
-            });
-            world.write_message(BroadcastRoomEvent {
-                from: player,
-                room: target_ent,
-                text: format!("{} arrives from {}.", name, dir.opposite().as_str_noun()),
-            });
+            reply(world, player, text);
+            if sneaking {
+                notify_observers(world, player, loc, &leave_text);
+                notify_observers(world, player, target_ent, &arrive_text);
+            } else {
+                let named = !cloaked && !stealthy;
+                world.write_message(BroadcastRoomEvent {
+                    from: player,
+                    room: loc,
+                    text: leave_text,
+                    unseen: named.then(|| format!("Someone leaves {}.", dir_key)),
+                });
+                world.write_message(BroadcastRoomEvent {
+                    from: player,
+                    room: target_ent,
+                    text: arrive_text,
+                    unseen: named
+                        .then(|| format!("Someone arrives from {}.", dir.opposite().as_str_noun())),
+                });
+            }
+            if spring_death_trap(world, player, target_ent) {
+                return;
+            }
+            record_visit(world, player, target_ent);
+            if let Some(id) = world.resource::<RoomRegistry>().id_of(target_ent) {
+                let trigger = QuestTrigger::Visit(id.to_string());
+                world.write_message(QuestEvent { player, trigger });
+            }
+            fire(world, target_ent, Some(player), TriggerKind::Enter, &[]);
+            lead_followers(world, player, loc, dir);
             return;
         } else {
-            world.write_message(OutputEvent {
+            reply(
+                world,
                 player,
-                text: "You arrive at an unknown location.".to_string(),
-            });
+                "You arrive at an unknown location.".to_string(),
+            );
             return;
         }
     }
 
-    world.write_message(OutputEvent {
+    reply(
+        world,
+        player,
+        format!("You can't go {} from here.", dir_key),
+    );
+}
+
+/// Drags everyone following `leader` out of `from` after it, each making its own move
+fn lead_followers(world: &mut World, leader: Entity, from: Entity, dir: StdExits) {
+    let followers: Vec<Entity> = world
+        .query_filtered::<(Entity, &Following, &Location), (Without<Fighting>, Without<Frozen>)>()
+        .iter(world)
+        .filter(|(_, f, loc)| f.0 == leader && loc.0 == from)
+        .map(|(ent, _, _)| ent)
+        .collect();
+    let name = world
+        .get::<Name>(leader)
+        .map_or("someone".to_string(), |n| n.0.clone());
+    for follower in followers {
+        if !can_see(world, follower, leader) {
+            continue;
+        }
+        reply(
+            world,
+            follower,
+            format!("You follow {} {}.", name, dir.as_str()),
+        );
+        cmd_move(dir, follower, world, "", &[]);
+    }
+}
+
+/// Awards exploration experience the first time a character enters a room
+fn record_visit(world: &mut World, player: Entity, room: Entity) {
+    let Some(id) = world
+        .resource::<RoomRegistry>()
+        .id_of(room)
+        .map(str::to_string)
+    else {
+        return;
+    };
+    if let Some(mut visited) = world.get_mut::<VisitedRooms>(player)
+        && visited.0.insert(id)
+    {
+        world.write_message(ExperienceEvent {
+            player,
+            amount: EXPLORE_XP,
+        });
+    }
+}
+
+pub fn cmd_open(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    set_door(player, world, args, false);
+}
+
+/// Opens or closes the door in one direction, along with its other side if it has one
+fn set_door(player: Entity, world: &mut World, args: &[&str], closed: bool) {
+    let verb = if closed { "close" } else { "open" };
+    let Some(dir) = args
+        .first()
+        .and_then(|a| StdExits::parse(&a.to_lowercase()))
+    else {
+        reply(world, player, format!("{} which way?", capitalize(verb)));
+        return;
+    };
+    let Some(loc) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    let state = world
+        .get::<Doors>(loc)
+        .and_then(|d| d.0.get(dir.as_str()).copied())
+        .filter(|_| !concealed(world, loc, dir.as_str()));
+    let text = match state {
+        None => format!("There is no door to {}.", dir.as_str_noun()),
+        Some(current) if current == closed => {
+            format!("That door is already {}.", verb_past(closed))
+        }
+        Some(_) => String::new(),
+    };
+    if !text.is_empty() {
+        reply(world, player, text);
+        return;
+    }
+
+    if let Some(mut doors) = world.get_mut::<Doors>(loc) {
+        doors.0.insert(dir.as_str().to_string(), closed);
+    }
+    let name = display_name(world, player);
+    reply(
+        world,
         player,
-        text: format!("You can't go {} from here.", dir_key),
+        format!("You {} the door to {}.", verb, dir.as_str_noun()),
+    );
+    world.write_message(BroadcastRoomEvent {
+        from: player,
+        room: loc,
+        text: format!("{} {}s the door to {}.", name, verb, dir.as_str_noun()),
+        unseen: Some(format!(
+            "Someone {}s the door to {}.",
+            verb,
+            dir.as_str_noun()
+        )),
     });
+
+    let back = dir.opposite().as_str();
+    let Some(target) = world
+        .get::<Room>(loc)
+        .and_then(|r| r.exits.get(dir.as_str()).copied())
+    else {
+        return;
+    };
+    if let Some(mut doors) = world.get_mut::<Doors>(target)
+        && let Some(state) = doors.0.get_mut(back)
+    {
+        *state = closed;
+        world.write_message(BroadcastRoomEvent {
+            from: player,
+            room: target,
+            text: format!("The door to {} {}s.", dir.opposite().as_str_noun(), verb),
+            unseen: None,
+        });
+    }
+}
+
+fn verb_past(closed: bool) -> &'static str {
+    if closed { "closed" } else { "open" }
 }
 
 pub fn cmd_north(player: Entity, world: &mut World, full: &str, args: &[&str]) {
     cmd_move(StdExits::North, player, world, full, args);
 }
 
+pub fn cmd_path(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(loc) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    let Some(query) = args.first() else {
+        reply(world, player, "Find a path to which room?".to_string());
+        return;
+    };
+    let zone = world
+        .get::<Zone>(loc)
+        .map_or(String::new(), |z| z.0.clone());
+    let target = world
+        .resource::<RoomRegistry>()
+        .get(&qualify(&zone, &query.to_lowercase()))
+        .or_else(|| find_landmark(world, query).map(|(_, room)| room));
+    let Some(target) = target else {
+        reply(
+            world,
+            player,
+            format!("There is no room or landmark called '{}'.", query),
+        );
+        return;
+    };
+
+    let name = world
+        .get::<Room>(target)
+        .map_or(String::new(), |r| r.name.clone());
+    let text = match find_path(world, loc, target) {
+        Some(route) if route.is_empty() => format!("You are already at {}.", name),
+        Some(route) => format!("Path to {}: {}.", name, describe_route(&route)),
+        None => format!("You can't find a way to {} from here.", name),
+    };
+    reply(world, player, text);
+}
+
 pub fn cmd_south(player: Entity, world: &mut World, full: &str, args: &[&str]) {
     cmd_move(StdExits::South, player, world, full, args);
 }
 
+pub fn cmd_travel(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(loc) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    let Some(query) = args.first() else {
+        let names: Vec<String> = landmarks(world).into_iter().map(|(n, _)| n).collect();
+        let text = if names.is_empty() {
+            "There are no landmarks to travel to.".to_string()
+        } else {
+            format!(
+                "Travel to one of: {}. Use 'travel stop' to halt.",
+                names.join(", ")
+            )
+        };
+        reply(world, player, text);
+        return;
+    };
+    if query.eq_ignore_ascii_case("stop") {
+        let text = if world.entity_mut(player).take::<Travelling>().is_some() {
+            "You stop travelling."
+        } else {
+            "You aren't travelling anywhere."
+        };
+        reply(world, player, text.to_string());
+        return;
+    }
+
+    let Some((landmark, target)) = find_landmark(world, query) else {
+        reply(
+            world,
+            player,
+            format!("There is no landmark called '{}'.", query),
+        );
+        return;
+    };
+    let text = match find_path(world, loc, target) {
+        Some(route) if route.is_empty() => format!("You are already at {}.", landmark),
+        Some(route) => {
+            let next_step = world.resource::<GameTick>().0 + TRAVEL_STEP_TICKS;
+            world.entity_mut(player).insert(Travelling {
+                destination: target,
+                next_step,
+            });
+            let steps = if route.len() == 1 { "step" } else { "steps" };
+            format!(
+                "You set off towards {} ({} {}).",
+                landmark,
+                route.len(),
+                steps
+            )
+        }
+        None => format!("You can't find a way to {} from here.", landmark),
+    };
+    reply(world, player, text);
+}
+
 pub fn cmd_west(player: Entity, world: &mut World, full: &str, args: &[&str]) {
     cmd_move(StdExits::West, player, world, full, args);
 }
 
 pub fn cmd_where(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
-    if let Some(loc) = world.get::<Location>(player) {
-        if let Some(zone_comp) = world.get::<Zone>(loc.0) {
-            let zone_id = &zone_comp.0;
-            if let Some(zin) = world.get_resource::<ZoneRegistry>() {
-                if let Some(zone_name) = zin.id_to_name.get(zone_id) {
-                    let text = format!("You are in: {}", zone_name);
-                    world.write_message(OutputEvent { player, text });
-                    return;
-                }
-            }
+    if let Some(loc) = world.get::<Location>(player)
+        && let Some(zone_comp) = world.get::<Zone>(loc.0)
+    {
+        let zone_id = &zone_comp.0;
+        if let Some(zin) = world.get_resource::<ZoneRegistry>()
+            && let Some(zone_name) = zin.id_to_name.get(zone_id)
+        {
+            let text = format!("You are in: {}", zone_name);
+            reply(world, player, text);
+            return;
         }
     }
 
     let text = "You are nowhere. (zone unknown)";
-    world.write_message(OutputEvent {
-        player,
-        text: text.to_string(),
-    });
+    reply(world, player, text.to_string());
 }
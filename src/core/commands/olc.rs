@@ -0,0 +1,261 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::movement::StdExits;
+use crate::core::commands::reply;
+use crate::core::components::{Location, Room};
+use crate::core::input::{EditTarget, open_editor};
+use crate::core::olc::{
+    add_room, create_zone, delete_room, mark_dirty, remove_exit, unique_room_id, valid_id, zone_of,
+};
+use crate::core::world::{RespawnPoint, RoomRegistry, ZoneRegistry, qualify, save_zone};
+
+use bevy_ecs::prelude::*;
+use tracing::error;
+
+/// The builder's room and the zone it belongs to
+fn here(world: &mut World, player: Entity) -> Option<(Entity, String)> {
+    let room = world.get::<Location>(player)?.0;
+    let found = zone_of(world, room).map(|zone| (room, zone));
+    if found.is_none() {
+        reply(world, player, "This room belongs to no zone.".to_string());
+    }
+    found
+}
+
+pub fn cmd_dig(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(dir) = args
+        .first()
+        .and_then(|d| StdExits::parse(&d.to_lowercase()))
+    else {
+        reply(
+            world,
+            player,
+            "Usage: dig <direction> <room name>".to_string(),
+        );
+        return;
+    };
+    if args.len() < 2 {
+        reply(
+            world,
+            player,
+            "Usage: dig <direction> <room name>".to_string(),
+        );
+        return;
+    }
+    let Some((room, zone)) = here(world, player) else {
+        return;
+    };
+    if world
+        .get::<Room>(room)
+        .is_some_and(|r| r.exits.contains_key(dir.as_str()))
+    {
+        reply(
+            world,
+            player,
+            format!("There is already an exit {}.", dir.as_str()),
+        );
+        return;
+    }
+
+    let name = args[1..].join(" ");
+    let id = unique_room_id(world, &zone, &name);
+    let new_room = add_room(world, &zone, &id, &name);
+    if let Some(mut r) = world.get_mut::<Room>(room) {
+        r.exits.insert(dir.as_str().to_string(), new_room);
+    }
+    if let Some(mut r) = world.get_mut::<Room>(new_room) {
+        r.exits.insert(dir.opposite().as_str().to_string(), room);
+    }
+    reply(
+        world,
+        player,
+        format!(
+            "You dig {} into {} ({}).",
+            dir.as_str(),
+            name,
+            qualify(&zone, &id)
+        ),
+    );
+}
+
+pub fn cmd_redit(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let usage = "Usage: redit name <name> | redit desc | redit exit <direction> <room id|none>";
+    let Some((room, zone)) = here(world, player) else {
+        return;
+    };
+
+    match args.first().map(|a| a.to_lowercase()).as_deref() {
+        Some("name") if args.len() > 1 => {
+            let name = args[1..].join(" ");
+            if let Some(mut r) = world.get_mut::<Room>(room) {
+                r.name = name.clone();
+            }
+            mark_dirty(world, &zone);
+            reply(world, player, format!("Room renamed to {}.", name));
+        }
+        Some("desc") => {
+            let lines = world
+                .get::<Room>(room)
+                .map(|r| r.description.lines().map(str::to_string).collect())
+                .unwrap_or_default();
+            open_editor(world, player, EditTarget::RoomDescription { room }, lines);
+        }
+        Some("exit") if args.len() == 3 => {
+            let Some(dir) = StdExits::parse(&args[1].to_lowercase()) else {
+                reply(world, player, format!("{} is not a direction.", args[1]));
+                return;
+            };
+            let (changed, text) = if args[2].eq_ignore_ascii_case("none") {
+                if remove_exit(world, room, dir.as_str()) {
+                    (true, format!("Removed the exit {}.", dir.as_str()))
+                } else {
+                    (false, format!("There is no exit {}.", dir.as_str()))
+                }
+            } else {
+                let id = qualify(&zone, &args[2].to_lowercase());
+                match world.resource::<RoomRegistry>().get(&id) {
+                    Some(target) => {
+                        if let Some(mut r) = world.get_mut::<Room>(room) {
+                            r.exits.insert(dir.as_str().to_string(), target);
+                        }
+                        (
+                            true,
+                            format!("The exit {} now leads to {}.", dir.as_str(), id),
+                        )
+                    }
+                    None => (false, format!("There is no room {}.", id)),
+                }
+            };
+            if changed {
+                mark_dirty(world, &zone);
+            }
+            reply(world, player, text);
+        }
+        _ => reply(world, player, usage.to_string()),
+    }
+}
+
+pub fn cmd_rdelete(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let Some((room, zone)) = here(world, player) else {
+        return;
+    };
+    let id = world
+        .resource::<RoomRegistry>()
+        .id_of(room)
+        .unwrap_or_default()
+        .to_string();
+    let zones = world.resource::<ZoneRegistry>();
+    if world.resource::<RespawnPoint>().0 == id || zones.respawns.get(&zone) == Some(&id) {
+        reply(
+            world,
+            player,
+            "Players respawn here; this room can't be deleted.".to_string(),
+        );
+        return;
+    }
+    let Some(fallback) = world
+        .get::<Room>(room)
+        .and_then(|r| r.exits.values().copied().find(|t| *t != room))
+    else {
+        reply(
+            world,
+            player,
+            "This room has no exits, so there's nowhere to move its contents.".to_string(),
+        );
+        return;
+    };
+
+    delete_room(world, room, fallback);
+    reply(
+        world,
+        player,
+        format!("You delete {} and step out into what remains.", id),
+    );
+}
+
+pub fn cmd_zcreate(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    if args.len() < 2 {
+        reply(world, player, "Usage: zcreate <id> <zone name>".to_string());
+        return;
+    }
+    let id = args[0].to_lowercase();
+    if !valid_id(&id) {
+        reply(
+            world,
+            player,
+            "Zone ids may only use lowercase letters, digits and underscores.".to_string(),
+        );
+        return;
+    }
+    if world
+        .resource::<ZoneRegistry>()
+        .id_to_name
+        .contains_key(&id)
+    {
+        reply(world, player, format!("Zone {} already exists.", id));
+        return;
+    }
+
+    let name = args[1..].join(" ");
+    let start = create_zone(world, &id, &name);
+    if let Some(mut loc) = world.get_mut::<Location>(player) {
+        loc.0 = start;
+    }
+    reply(
+        world,
+        player,
+        format!(
+            "Zone {} created. You step into {}:start; 'zsave' writes it to disk.",
+            name, id
+        ),
+    );
+}
+
+pub fn cmd_zsave(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let zones: Vec<String> = match args.first() {
+        Some(a) if a.eq_ignore_ascii_case("all") => {
+            let mut dirty: Vec<String> = world
+                .resource::<ZoneRegistry>()
+                .dirty
+                .iter()
+                .cloned()
+                .collect();
+            dirty.sort();
+            dirty
+        }
+        Some(a) => vec![a.to_lowercase()],
+        None => match here(world, player) {
+            Some((_, zone)) => vec![zone],
+            None => return,
+        },
+    };
+    if zones.is_empty() {
+        reply(world, player, "No zones have unsaved changes.".to_string());
+        return;
+    }
+
+    for zone in zones {
+        let text = match save_zone(world, &zone) {
+            Ok(path) => format!("Saved zone {} to {}.", zone, path.display()),
+            Err(e) => {
+                error!("Failed to save zone {}: {:?}", zone, e);
+                format!("Could not save zone {}: {}", zone, e)
+            }
+        };
+        reply(world, player, text);
+    }
+}
@@ -0,0 +1,266 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::items::items_held_by;
+use crate::core::commands::reply;
+use crate::core::components::{Credits, Location, Value};
+use crate::core::economy::{Shop, TransactionLog};
+use crate::core::events::{BroadcastRoomEvent, SaveEvent};
+use crate::core::text::{capitalize, display_name};
+use crate::core::world::spawn_item;
+
+use bevy_ecs::prelude::*;
+
+/// First shopkeeper standing in the player's room
+fn shopkeeper(world: &mut World, player: Entity) -> Option<Entity> {
+    let room = world.get::<Location>(player)?.0;
+    let found = world
+        .query_filtered::<(Entity, &Location), With<Shop>>()
+        .iter(world)
+        .find(|(_, loc)| loc.0 == room)
+        .map(|(ent, _)| ent);
+    if found.is_none() {
+        reply(
+            world,
+            player,
+            "There is no one here to trade with.".to_string(),
+        );
+    }
+    found
+}
+
+pub fn cmd_list(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let Some(keeper) = shopkeeper(world, player) else {
+        return;
+    };
+    let Some(shop) = world.get::<Shop>(keeper) else {
+        return;
+    };
+
+    let lines: Vec<String> = shop
+        .def
+        .stock
+        .iter()
+        .zip(&shop.remaining)
+        .map(|(stock, left)| {
+            let qty = if *left == 0 {
+                "sold out".to_string()
+            } else {
+                format!("{} left", left)
+            };
+            format!(
+                "  {:<30} {:>6} credits  ({})",
+                stock.item.name,
+                shop.buy_price(stock.item.value),
+                qty
+            )
+        })
+        .collect();
+    let text = if lines.is_empty() {
+        format!(
+            "{} has nothing for sale.",
+            capitalize(&display_name(world, keeper))
+        )
+    } else {
+        format!(
+            "{} offers:\n{}",
+            capitalize(&display_name(world, keeper)),
+            lines.join("\n")
+        )
+    };
+    reply(world, player, text);
+}
+
+pub fn cmd_buy(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(keyword) = args.first() else {
+        reply(world, player, "Buy what?".to_string());
+        return;
+    };
+    let Some(keeper) = shopkeeper(world, player) else {
+        return;
+    };
+    let Some(shop) = world.get::<Shop>(keeper) else {
+        return;
+    };
+    let Some((index, stock)) = shop
+        .def
+        .stock
+        .iter()
+        .enumerate()
+        .find(|(_, s)| s.item.matches(keyword))
+    else {
+        reply(world, player, "That isn't for sale here.".to_string());
+        return;
+    };
+    let item = stock.item.clone();
+    let price = shop.buy_price(item.value);
+    if shop.remaining[index] == 0 {
+        reply(
+            world,
+            player,
+            format!("{} is sold out.", capitalize(&item.name)),
+        );
+        return;
+    }
+
+    let credits = world.get::<Credits>(player).map_or(0, |c| c.0);
+    if credits < price {
+        reply(
+            world,
+            player,
+            format!("You can't afford {} ({} credits).", item.name, price),
+        );
+        return;
+    }
+
+    if let Some(mut c) = world.get_mut::<Credits>(player) {
+        c.0 -= price;
+    }
+    if let Some(mut shop) = world.get_mut::<Shop>(keeper) {
+        shop.remaining[index] -= 1;
+    }
+    spawn_item(world, &item, player);
+
+    let name = display_name(world, player);
+    let keeper_name = display_name(world, keeper);
+    world.resource_mut::<TransactionLog>().record(format!(
+        "BUY {} bought {} from {} for {}",
+        name, item.name, keeper_name, price
+    ));
+    reply(
+        world,
+        player,
+        format!("You buy {} for {} credits.", item.name, price),
+    );
+    if let Some(room) = world.get::<Location>(player).map(|l| l.0) {
+        world.write_message(BroadcastRoomEvent {
+            from: player,
+            room,
+            text: format!("{} buys {}.", name, item.name),
+            unseen: Some(format!("Someone buys {}.", item.name)),
+        });
+    }
+    world.write_message(SaveEvent { player });
+}
+
+/// The carried item matching `keyword`, with what this shop would pay for it
+fn appraise(
+    world: &mut World,
+    player: Entity,
+    keeper: Entity,
+    keyword: &str,
+) -> Option<(Entity, String, u64)> {
+    let Some((ent, item)) = items_held_by(world, player)
+        .into_iter()
+        .find(|(_, item)| item.matches(keyword))
+    else {
+        reply(world, player, "You aren't carrying that.".to_string());
+        return None;
+    };
+    let price = match (world.get::<Value>(ent), world.get::<Shop>(keeper)) {
+        (Some(value), Some(shop)) => shop.sell_price(value.0),
+        _ => 0,
+    };
+    if price == 0 {
+        reply(
+            world,
+            player,
+            format!(
+                "{} isn't interested in {}.",
+                capitalize(&display_name(world, keeper)),
+                item.name
+            ),
+        );
+        return None;
+    }
+    Some((ent, item.name, price))
+}
+
+pub fn cmd_sell(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(keyword) = args.first() else {
+        reply(world, player, "Sell what?".to_string());
+        return;
+    };
+    let Some(keeper) = shopkeeper(world, player) else {
+        return;
+    };
+    let Some((ent, item_name, price)) = appraise(world, player, keeper, keyword) else {
+        return;
+    };
+
+    world.despawn(ent);
+    if let Some(mut c) = world.get_mut::<Credits>(player) {
+        c.0 += price;
+    }
+
+    let name = display_name(world, player);
+    let keeper_name = display_name(world, keeper);
+    world.resource_mut::<TransactionLog>().record(format!(
+        "SELL {} sold {} to {} for {}",
+        name, item_name, keeper_name, price
+    ));
+    reply(
+        world,
+        player,
+        format!("You sell {} for {} credits.", item_name, price),
+    );
+    if let Some(room) = world.get::<Location>(player).map(|l| l.0) {
+        world.write_message(BroadcastRoomEvent {
+            from: player,
+            room,
+            text: format!("{} sells {}.", name, item_name),
+            unseen: Some(format!("Someone sells {}.", item_name)),
+        });
+    }
+    world.write_message(SaveEvent { player });
+}
+
+pub fn cmd_value(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(keyword) = args.first() else {
+        reply(world, player, "Value what?".to_string());
+        return;
+    };
+    let Some(keeper) = shopkeeper(world, player) else {
+        return;
+    };
+    if let Some((_, item_name, price)) = appraise(world, player, keeper, keyword) {
+        reply(
+            world,
+            player,
+            format!(
+                "{} would pay {} credits for {}.",
+                capitalize(&display_name(world, keeper)),
+                price,
+                item_name
+            ),
+        );
+    }
+}
+
+pub fn cmd_transactions(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let count = args
+        .first()
+        .and_then(|a| a.parse::<usize>().ok())
+        .unwrap_or(20);
+    let log = world.resource::<TransactionLog>();
+    let lines: Vec<String> = log.recent.iter().rev().take(count).rev().cloned().collect();
+    let text = if lines.is_empty() {
+        "No transactions recorded.".to_string()
+    } else {
+        format!("Recent transactions:\n{}", lines.join("\n"))
+    };
+    reply(world, player, text);
+}
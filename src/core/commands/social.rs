@@ -14,52 +14,91 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::core::components::{Location, Name, Zone};
-use crate::core::events::{BroadcastRoomEvent, BroadcastZoneEvent, OutputEvent};
+use crate::core::commands::{find_in_room, find_player, is_muted, reply};
+use crate::core::components::{Ignoring, Location, Name, ReplyTo, Zone};
+use crate::core::dialogue::{choose_option, start_conversation};
+use crate::core::events::{BroadcastRoomEvent, BroadcastZoneEvent, EmoteEvent, SaveEvent};
+use crate::core::npc::react_to_speech;
+use crate::core::scripting::{TriggerKind, fire};
+use crate::core::stealth::reveal;
+use crate::core::text::{capitalize, display_name};
 
 use bevy_ecs::prelude::*;
 
 pub fn cmd_say(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
     if args.is_empty() {
-        world.write_message(OutputEvent {
-            player,
-            text: "Say what?".to_string(),
-        });
+        reply(world, player, "Say what?".to_string());
         return;
     }
+    if is_muted(world, player) {
+        return;
+    }
+    reveal(world, player);
 
     let message = args.join(" ");
-    let name = match world.get::<Name>(player) {
-        Some(n) => n.0.clone(),
-        None => "Someone".to_string(),
-    };
+    let name = display_name(world, player);
 
-    if let Some(loc) = world.get::<Location>(player) {
+    let room = world.get::<Location>(player).map(|l| l.0);
+    if let Some(room) = room {
         world.write_message(BroadcastRoomEvent {
             from: player,
-            room: loc.0,
+            room,
             text: format!("{} says: {}", name, message),
+            unseen: Some(format!("Someone says: {}", message)),
         });
     } else {
-        world.write_message(OutputEvent {
+        reply(
+            world,
             player,
-            text: "Your words echo into the void... there is no one around to hear them."
-                .to_string(),
-        });
+            "Your words echo into the void... there is no one around to hear them.".to_string(),
+        );
         return;
     }
-    world.write_message(OutputEvent {
-        player,
-        text: format!("You say: {}", message),
+    reply(world, player, format!("You say: {}", message));
+
+    if let Some(room) = room {
+        react_to_speech(world, room, player, &message);
+        fire(
+            world,
+            room,
+            Some(player),
+            TriggerKind::Say,
+            &[("message", message.into())],
+        );
+    }
+}
+
+pub fn cmd_emote(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    if args.is_empty() {
+        reply(world, player, "Emote what?".to_string());
+        return;
+    }
+    if is_muted(world, player) {
+        return;
+    }
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    reveal(world, player);
+    let name = display_name(world, player);
+
+    let text = format!("{} {}", name, args.join(" "));
+    reply(world, player, text.clone());
+    world.write_message(EmoteEvent {
+        from: player,
+        target: None,
+        room,
+        text,
+        unseen: Some(format!("Someone {}", args.join(" "))),
     });
 }
 
 pub fn cmd_shout(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
     if args.is_empty() {
-        world.write_message(OutputEvent {
-            player,
-            text: "Shout what?".to_string(),
-        });
+        reply(world, player, "Shout what?".to_string());
+        return;
+    }
+    if is_muted(world, player) {
         return;
     }
 
@@ -73,18 +112,140 @@ pub fn cmd_shout(player: Entity, world: &mut World, _full: &str, args: &[&str])
     } else {
         "".to_string()
     };
-    let name = match world.get::<Name>(player) {
-        Some(n) => n.0.clone(),
-        None => "Someone".to_string(),
-    };
+    reveal(world, player);
+    let name = display_name(world, player);
 
     world.write_message(BroadcastZoneEvent {
         from: player,
         zone: player_zone,
         text: format!("{} shouts: {}", name, message),
     });
-    world.write_message(OutputEvent {
+    reply(world, player, format!("You shout: {}", message));
+}
+
+pub fn cmd_talk(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(target) = args.first() else {
+        reply(world, player, "Talk to whom?".to_string());
+        return;
+    };
+    if let Ok(choice) = target.parse::<usize>() {
+        choose_option(world, player, choice);
+        return;
+    }
+
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    let Some(npc) = find_in_room(world, room, target, player) else {
+        reply(world, player, "They aren't here.".to_string());
+        return;
+    };
+    if !start_conversation(world, player, npc) {
+        reply(
+            world,
+            player,
+            "They have nothing to say to you.".to_string(),
+        );
+    }
+}
+
+/// Delivers a private message, telling the sender when it can't be delivered
+fn send_tell(world: &mut World, player: Entity, target: &str, message: &str) {
+    if is_muted(world, player) {
+        return;
+    }
+    let Some(name) = world.get::<Name>(player).map(|n| n.0.clone()) else {
+        return;
+    };
+    let Some(recipient) = find_player(world, target) else {
+        reply(
+            world,
+            player,
+            format!("{} is not online.", capitalize(target)),
+        );
+        return;
+    };
+    let recipient_name = world
+        .get::<Name>(recipient)
+        .map_or(target.to_string(), |n| n.0.clone());
+    if recipient == player {
+        reply(world, player, "You mutter to yourself.".to_string());
+        return;
+    }
+    if world
+        .get::<Ignoring>(recipient)
+        .is_some_and(|i| i.contains(&name))
+    {
+        reply(
+            world,
+            player,
+            format!("{} is ignoring you.", recipient_name),
+        );
+        return;
+    }
+
+    reply(world, recipient, format!("{} tells you: {}", name, message));
+    reply(
+        world,
         player,
-        text: format!("You shout: {}", message),
-    });
+        format!("You tell {}: {}", recipient_name, message),
+    );
+    world.entity_mut(recipient).insert(ReplyTo(name));
+}
+
+pub fn cmd_tell(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    if args.len() < 2 {
+        reply(world, player, "Tell whom what?".to_string());
+        return;
+    }
+    send_tell(world, player, args[0], &args[1..].join(" "));
+}
+
+pub fn cmd_reply(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(target) = world.get::<ReplyTo>(player).map(|r| r.0.clone()) else {
+        reply(world, player, "No one has sent you a tell.".to_string());
+        return;
+    };
+    if args.is_empty() {
+        reply(world, player, format!("Reply to {} with what?", target));
+        return;
+    }
+    send_tell(world, player, &target, &args.join(" "));
+}
+
+pub fn cmd_ignore(player: Entity, world: &mut World, _full: &str, args: &[&str]) {
+    let Some(target) = args.first() else {
+        let mut names: Vec<String> = world
+            .get::<Ignoring>(player)
+            .map(|i| i.0.iter().map(|n| capitalize(n)).collect())
+            .unwrap_or_default();
+        names.sort();
+        let text = if names.is_empty() {
+            "You aren't ignoring anyone.".to_string()
+        } else {
+            format!("You are ignoring: {}", names.join(", "))
+        };
+        reply(world, player, text);
+        return;
+    };
+
+    let key = target.to_lowercase();
+    if world
+        .get::<Name>(player)
+        .is_some_and(|n| n.0.to_lowercase() == key)
+    {
+        reply(world, player, "You can't ignore yourself.".to_string());
+        return;
+    }
+    let Some(mut ignoring) = world.get_mut::<Ignoring>(player) else {
+        return;
+    };
+    let text = if ignoring.0.remove(&key) {
+        format!("You are no longer ignoring {}.", capitalize(&key))
+    } else {
+        ignoring.0.insert(key.clone());
+        format!("You are now ignoring {}.", capitalize(&key))
+    };
+    reply(world, player, text);
+    world.write_message(SaveEvent { player });
 }
@@ -0,0 +1,140 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::combat::Fighting;
+use crate::core::commands::{reply, spend_stamina};
+use crate::core::components::{ConcealedExits, Hidden, Location, Sneaking};
+use crate::core::events::BroadcastRoomEvent;
+use crate::core::stealth::{notify_observers, perception_score, roll, stealth_score};
+use crate::core::text::display_name;
+
+use bevy_ecs::prelude::*;
+
+const HIDE_STAMINA: u32 = 5;
+const SEARCH_STAMINA: u32 = 5;
+/// Perception roll needed to turn up a concealed exit.
+const HIDDEN_EXIT_DIFFICULTY: i32 = 25;
+
+pub fn cmd_sneak(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let text = if world.entity_mut(player).take::<Sneaking>().is_some() {
+        "You stop sneaking."
+    } else {
+        world.entity_mut(player).insert(Sneaking);
+        "You begin moving quietly."
+    };
+    reply(world, player, text.to_string());
+}
+
+pub fn cmd_hide(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    let attacked = world
+        .query::<&Fighting>()
+        .iter(world)
+        .any(|f| f.0 == player);
+    if attacked || world.get::<Fighting>(player).is_some() {
+        reply(
+            world,
+            player,
+            "You can't hide in the middle of a fight!".to_string(),
+        );
+        return;
+    }
+    if world.get::<Hidden>(player).is_some() {
+        reply(world, player, "You are already hidden.".to_string());
+        return;
+    }
+    if !spend_stamina(world, player, HIDE_STAMINA) {
+        return;
+    }
+
+    let name = display_name(world, player);
+    notify_observers(
+        world,
+        player,
+        room,
+        &format!("You notice {} slipping into the shadows.", name),
+    );
+    let difficulty = roll(stealth_score(world, player));
+    world.entity_mut(player).insert(Hidden { difficulty });
+    reply(
+        world,
+        player,
+        "You find a spot out of sight and settle into it.".to_string(),
+    );
+}
+
+pub fn cmd_search(player: Entity, world: &mut World, _full: &str, _args: &[&str]) {
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    if !spend_stamina(world, player, SEARCH_STAMINA) {
+        return;
+    }
+    let name = display_name(world, player);
+    world.write_message(BroadcastRoomEvent {
+        from: player,
+        room,
+        text: format!("{} searches the area carefully.", name),
+        unseen: Some("Someone searches the area carefully.".to_string()),
+    });
+
+    let perception = perception_score(world, player);
+    let hiding: Vec<(Entity, i32)> = world
+        .query::<(Entity, &Location, &Hidden)>()
+        .iter(world)
+        .filter(|(ent, loc, _)| *ent != player && loc.0 == room)
+        .map(|(ent, _, hidden)| (ent, hidden.difficulty))
+        .collect();
+    let found: Vec<Entity> = hiding
+        .into_iter()
+        .filter(|&(_, difficulty)| roll(perception) > difficulty)
+        .map(|(ent, _)| ent)
+        .collect();
+    for &ent in &found {
+        world.entity_mut(ent).remove::<Hidden>();
+        let hider = display_name(world, ent);
+        reply(world, player, format!("You find {} hiding here!", hider));
+        reply(world, ent, format!("{} has found your hiding spot!", name));
+    }
+
+    let mut exits: Vec<String> = Vec::new();
+    if world.get::<ConcealedExits>(room).is_some()
+        && roll(perception) >= HIDDEN_EXIT_DIFFICULTY
+        && let Some(concealed) = world.entity_mut(room).take::<ConcealedExits>()
+    {
+        exits = concealed.0.into_iter().collect();
+        exits.sort();
+    }
+    for dir in &exits {
+        reply(world, player, format!("You discover a hidden way {}!", dir));
+        world.write_message(BroadcastRoomEvent {
+            from: player,
+            room,
+            text: format!("{} uncovers a hidden way {}.", name, dir),
+            unseen: Some(format!("Someone uncovers a hidden way {}.", dir)),
+        });
+    }
+
+    if found.is_empty() && exits.is_empty() {
+        reply(
+            world,
+            player,
+            "You search around but find nothing out of the ordinary.".to_string(),
+        );
+    }
+}
@@ -14,10 +14,11 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt;
 
 use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 // === Network Components ===
@@ -25,6 +26,23 @@ use tokio::sync::mpsc;
 #[derive(Component)]
 pub struct OutputTx(pub mpsc::UnboundedSender<String>);
 
+/// The most recent lines delivered to a player, kept as evidence for `report`.
+#[derive(Component, Debug, Default)]
+pub struct Scrollback(pub VecDeque<String>);
+
+impl Scrollback {
+    pub const LIMIT: usize = 50;
+
+    pub fn push(&mut self, text: &str) {
+        for line in text.lines() {
+            if self.0.len() == Self::LIMIT {
+                self.0.pop_front();
+            }
+            self.0.push_back(line.to_string());
+        }
+    }
+}
+
 // === Player Components ===
 
 #[derive(Component)]
@@ -42,12 +60,13 @@ impl fmt::Display for Name {
 #[derive(Clone, Component, Debug, Eq, PartialEq)]
 pub enum PlayerState {
     ChoosingName,
+    EnteringPassword,
     ChoosingPassword,
     ChoosingRace,
     Active,
 }
 
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
 pub enum Race {
     Alien,
     Human,
@@ -55,12 +74,152 @@ pub enum Race {
     SyntheticHuman,
 }
 
+#[derive(Component)]
+pub struct PasswordHash(pub String);
+
+/// Lowercased names of players whose messages are hidden from this one.
+#[derive(Component, Debug, Default)]
+pub struct Ignoring(pub HashSet<String>);
+
+impl Ignoring {
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains(&name.to_lowercase())
+    }
+}
+
+/// Name of whoever last sent this player a tell, for `reply`.
+#[derive(Component, Debug)]
+pub struct ReplyTo(pub String);
+
+/// Grants access to administrative commands.
+#[derive(Component)]
+pub struct Admin;
+
+/// Grants access to the world-building commands.
+#[derive(Component)]
+pub struct Builder;
+
+/// Silenced by a moderator until the given unix time, in seconds.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Muted {
+    pub until: u64,
+}
+
+/// Frozen by a moderator; every command but `quit` is refused.
+#[derive(Component)]
+pub struct Frozen;
+
 #[derive(Component, Debug, Default, Clone)]
 pub struct RegistrationData {
     pub chosen_name: Option<String>,
     pub password_hash: Option<String>,
 }
 
+// === Character Components ===
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Attributes {
+    pub strength: u32,
+    pub agility: u32,
+    pub intellect: u32,
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Experience {
+    pub level: u32,
+    pub xp: u64,
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self { level: 1, xp: 0 }
+    }
+}
+
+/// Registry ids of every room the character has set foot in.
+#[derive(Component, Debug, Default)]
+pub struct VisitedRooms(pub HashSet<String>);
+
+/// Spendable currency.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct Credits(pub u64);
+
+/// Story flags set through dialogue and quests.
+#[derive(Component, Debug, Default)]
+pub struct Flags(pub HashSet<String>);
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Stamina {
+    pub current: u32,
+    pub max: u32,
+}
+
+/// Ability name -> tick at which the ability may be used again.
+#[derive(Component, Debug, Default)]
+pub struct Cooldowns(pub HashMap<&'static str, u64>);
+
+#[derive(Component)]
+pub struct Cloaked;
+
+/// Moving carefully; observers must beat a stealth check to notice.
+#[derive(Component)]
+pub struct Sneaking;
+
+/// Tucked out of sight until found or until giving the position away.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Hidden {
+    /// Stealth roll a searcher has to beat.
+    pub difficulty: i32,
+}
+
+// === Item Components ===
+
+#[derive(Component, Debug, Clone)]
+pub struct Item {
+    pub name: String,
+    pub keywords: Vec<String>,
+    pub description: String,
+}
+
+impl Item {
+    pub fn matches(&self, word: &str) -> bool {
+        keywords_match(&self.keywords, word)
+    }
+}
+
+/// Whether any keyword starts with `word`, ignoring case
+pub fn keywords_match(keywords: &[String], word: &str) -> bool {
+    let word = word.to_lowercase();
+    keywords.iter().any(|k| k.starts_with(&word))
+}
+
+/// Gives off enough light to see by, whether carried or lying on the floor.
+#[derive(Component)]
+pub struct LightSource;
+
+/// Base price of an item before any shop markup.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Value(pub u32);
+
+/// The entity (player, NPC or container) carrying this item.
+#[derive(Component)]
+pub struct HeldBy(pub Entity);
+
+/// Placed by a zone file; holds "zone:room#n" so resets can tell whether it's still around.
+#[derive(Component, Debug, Clone)]
+pub struct Placed(pub String);
+
+#[derive(Component)]
+pub struct Corpse {
+    pub decay_at: u64,
+}
+
 // === World Components ===
 
 #[derive(Component)]
@@ -70,8 +229,72 @@ pub struct Room {
     pub exits: HashMap<String, Entity>,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RoomFlag {
+    /// Nothing in the room can be made out without a light.
+    Dark,
+    /// Always lit, even outdoors at night.
+    Lit,
+    /// No fighting of any kind.
+    Safe,
+    /// Sheltered from the sky and the weather.
+    Indoors,
+    /// Scripts can't move anyone into or out of the room.
+    NoTeleport,
+    /// Kills whoever walks in.
+    DeathTrap,
+    /// No air; hurts anyone who needs to breathe.
+    Vacuum,
+    /// Toxic or irradiated; hurts anyone without protection.
+    Hazard,
+}
+
+#[derive(Component, Debug, Default)]
+pub struct RoomFlags(pub HashSet<RoomFlag>);
+
+impl RoomFlags {
+    pub fn has(&self, flag: RoomFlag) -> bool {
+        self.0.contains(&flag)
+    }
+}
+
+/// Scenery in a room that can be looked at but not taken.
+#[derive(Debug, Clone)]
+pub struct Extra {
+    pub keywords: Vec<String>,
+    pub description: String,
+}
+
+impl Extra {
+    pub fn matches(&self, word: &str) -> bool {
+        keywords_match(&self.keywords, word)
+    }
+}
+
+#[derive(Component, Debug, Default)]
+pub struct Extras(pub Vec<Extra>);
+
 #[derive(Component)]
 pub struct Location(pub Entity);
 
 #[derive(Component)]
 pub struct Zone(pub String);
+
+/// Exits fitted with a door, mapped to whether it currently stands closed.
+#[derive(Component, Debug, Default)]
+pub struct Doors(pub HashMap<String, bool>);
+
+impl Doors {
+    pub fn is_closed(&self, dir: &str) -> bool {
+        self.0.get(dir).copied().unwrap_or(false)
+    }
+}
+
+/// Exits nobody has found yet; they can't be seen or used until searched out.
+#[derive(Component, Debug, Default)]
+pub struct ConcealedExits(pub HashSet<String>);
+
+/// A well-known name players can `travel` to.
+#[derive(Component, Debug, Clone)]
+pub struct Landmark(pub String);
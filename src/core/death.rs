@@ -0,0 +1,210 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::combat::Fighting;
+use crate::core::commands::reply;
+use crate::core::components::{
+    Cloaked, Corpse, Experience, Health, HeldBy, Item, Location, Name, Player, Room, Zone,
+};
+use crate::core::effects::{EffectKind, Effects};
+use crate::core::events::{
+    ApplyEffectEvent, BroadcastRoomEvent, DeathEvent, QuestEvent, SaveEvent,
+};
+use crate::core::groups::{distribute_loot, share_experience};
+use crate::core::npc::Npc;
+use crate::core::quests::QuestTrigger;
+use crate::core::text::{capitalize, display_name};
+use crate::core::world::{GameTick, RespawnPoint, RoomRegistry, TICKS_PER_SECOND, ZoneRegistry};
+
+use bevy_ecs::prelude::*;
+use tracing::error;
+
+/// Two minutes.
+const CORPSE_DECAY_TICKS: u64 = 2 * 60 * TICKS_PER_SECOND;
+/// One minute.
+const WEAKNESS_TICKS: u64 = 60 * TICKS_PER_SECOND;
+/// Experience per level of a slain NPC awarded to the killer, shared with their group.
+const KILL_XP_PER_LEVEL: u64 = 50;
+
+/// Runs the death pipeline for everyone killed this tick
+pub fn handle_deaths(world: &mut World) {
+    let deaths: Vec<(Entity, Option<Entity>)> = world
+        .resource_mut::<Messages<DeathEvent>>()
+        .drain()
+        .map(|e| (e.victim, e.killer))
+        .collect();
+
+    for (victim, killer) in deaths {
+        kill(world, victim, killer);
+    }
+}
+
+fn kill(world: &mut World, victim: Entity, killer: Option<Entity>) {
+    let Some(room) = world.get::<Location>(victim).map(|l| l.0) else {
+        return;
+    };
+    let name = display_name(world, victim);
+    let killer = killer.filter(|k| *k != victim);
+    let killer_name = killer
+        .and_then(|k| world.get::<Name>(k))
+        .map(|n| n.0.clone());
+
+    match &killer_name {
+        Some(k) => {
+            reply(world, victim, format!("You have been slain by {}!", k));
+            world.write_message(BroadcastRoomEvent {
+                from: victim,
+                room,
+                text: format!("{} has been slain by {}!", capitalize(&name), k),
+                unseen: Some(format!("Someone has been slain by {}!", k)),
+            });
+        }
+        None => {
+            reply(world, victim, "You have died.".to_string());
+            world.write_message(BroadcastRoomEvent {
+                from: victim,
+                room,
+                text: format!("{} dies.", capitalize(&name)),
+                unseen: Some("Someone dies.".to_string()),
+            });
+        }
+    }
+
+    let decay_at = world.resource::<GameTick>().0 + CORPSE_DECAY_TICKS;
+    let corpse = world
+        .spawn((
+            Item {
+                name: format!("the corpse of {}", name),
+                keywords: vec!["corpse".to_string(), name.to_lowercase()],
+                description: format!("The lifeless remains of {} lie here.", name),
+            },
+            Corpse { decay_at },
+            Location(room),
+        ))
+        .id();
+
+    let belongings: Vec<Entity> = world
+        .query::<(Entity, &HeldBy)>()
+        .iter(world)
+        .filter(|(_, held)| held.0 == victim)
+        .map(|(ent, _)| ent)
+        .collect();
+    let looted = world.get::<Player>(victim).is_none()
+        && killer.is_some_and(|k| distribute_loot(world, k, room, &belongings));
+    if !looted {
+        for item in belongings {
+            world.entity_mut(item).insert(HeldBy(corpse));
+        }
+    }
+
+    if let Some(k) = killer
+        && let Some(template) = world.get::<Npc>(victim).map(|n| n.template.clone())
+    {
+        world.write_message(QuestEvent {
+            player: k,
+            trigger: QuestTrigger::Kill(template),
+        });
+    }
+    if let Some(k) = killer
+        && world.get::<Player>(victim).is_none()
+    {
+        let level = world.get::<Experience>(victim).map_or(1, |e| e.level);
+        share_experience(world, k, room, KILL_XP_PER_LEVEL * u64::from(level));
+    }
+
+    if world.get::<Player>(victim).is_none() {
+        world.despawn(victim);
+        return;
+    }
+    respawn(world, victim, room, &name);
+}
+
+fn respawn(world: &mut World, player: Entity, died_in: Entity, name: &str) {
+    let zone_respawn = world
+        .get::<Zone>(died_in)
+        .and_then(|z| world.resource::<ZoneRegistry>().respawns.get(&z.0).cloned());
+    let id = zone_respawn.unwrap_or_else(|| world.resource::<RespawnPoint>().0.clone());
+    let dest = match world.resource::<RoomRegistry>().get(&id) {
+        Some(d) => d,
+        None => {
+            error!("Respawn room {} does not exist", id);
+            died_in
+        }
+    };
+
+    let mut ent = world.entity_mut(player);
+    ent.insert(Location(dest));
+    ent.remove::<(Cloaked, Fighting)>();
+    if let Some(mut effects) = ent.get_mut::<Effects>() {
+        effects.0.clear();
+    }
+    if let Some(mut health) = ent.get_mut::<Health>() {
+        health.current = (health.max / 2).max(1);
+    }
+
+    let room_name = world
+        .get::<Room>(dest)
+        .map(|r| r.name.clone())
+        .unwrap_or_default();
+    reply(
+        world,
+        player,
+        format!("You awaken in {}, weak and disoriented.", room_name),
+    );
+    world.write_message(ApplyEffectEvent {
+        target: player,
+        kind: EffectKind::Weakened,
+        duration: WEAKNESS_TICKS,
+        magnitude: 0,
+        source: None,
+    });
+    world.write_message(BroadcastRoomEvent {
+        from: player,
+        room: dest,
+        text: format!("{} materializes, gasping for breath.", name),
+        unseen: Some("Someone materializes, gasping for breath.".to_string()),
+    });
+    world.write_message(SaveEvent { player });
+}
+
+/// Crumbles corpses, and whatever is left inside them, once their timer runs out
+pub fn decay_corpses(
+    mut commands: Commands,
+    tick: Res<GameTick>,
+    corpses: Query<(Entity, &Item, &Corpse, Option<&Location>)>,
+    contents: Query<(Entity, &HeldBy)>,
+    mut room_events: MessageWriter<BroadcastRoomEvent>,
+) {
+    for (ent, item, corpse, loc) in corpses.iter() {
+        if tick.0 < corpse.decay_at {
+            continue;
+        }
+        if let Some(loc) = loc {
+            room_events.write(BroadcastRoomEvent {
+                from: ent,
+                room: loc.0,
+                text: format!("{} crumbles to dust.", capitalize(&item.name)),
+                unseen: None,
+            });
+        }
+        for (inner, held) in contents.iter() {
+            if held.0 == ent {
+                commands.entity(inner).despawn();
+            }
+        }
+        commands.entity(ent).despawn();
+    }
+}
@@ -0,0 +1,389 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use crate::core::commands::items::items_held_by;
+use crate::core::commands::reply;
+use crate::core::components::{Flags, Location, Race};
+use crate::core::events::{BroadcastRoomEvent, QuestEvent};
+use crate::core::npc::Npc;
+use crate::core::quests::{QuestLog, QuestTrigger, accept_quest};
+use crate::core::text::{capitalize, display_name};
+use crate::core::world::{ItemDef, is_default, qualify, spawn_item};
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// === Dialogue Definitions ===
+
+/// Requirements an option must meet before it is offered.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct ConditionDef {
+    /// Offered only to these races; empty allows everyone.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub race: Vec<Race>,
+    /// Keyword of an item the player must be carrying.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub has_item: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub not_flag: Option<String>,
+    /// Quest the player has neither started nor finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quest_available: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quest_active: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quest_done: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DialogueAction {
+    SetFlag {
+        flag: String,
+    },
+    ClearFlag {
+        flag: String,
+    },
+    /// Hands over an item once; `flag` is set on the player and withholds it afterwards.
+    GiveItem {
+        name: String,
+        keywords: Vec<String>,
+        description: String,
+        #[serde(default, skip_serializing_if = "is_default")]
+        value: u32,
+        #[serde(default, skip_serializing_if = "is_default")]
+        light: bool,
+        flag: String,
+    },
+    TakeItem {
+        keyword: String,
+    },
+    AcceptQuest {
+        quest: String,
+    },
+    /// Room-visible narration, e.g. "nods slowly."; prefixed with the NPC's name.
+    Emote {
+        text: String,
+    },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OptionDef {
+    pub text: String,
+    /// Node to continue with; the conversation ends when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub conditions: ConditionDef,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<DialogueAction>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NodeDef {
+    pub id: String,
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<OptionDef>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct DialogueDef {
+    pub id: String,
+    pub start: String,
+    pub nodes: Vec<NodeDef>,
+}
+
+impl DialogueDef {
+    pub fn node(&self, id: &str) -> Option<&NodeDef> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+}
+
+/// Every loaded dialogue tree, keyed by "zone:id".
+#[derive(Resource, Default)]
+pub struct DialogueRegistry(pub HashMap<String, DialogueDef>);
+
+// === Dialogue Components ===
+
+/// Registry id of the dialogue tree an NPC speaks.
+#[derive(Component)]
+pub struct Dialogue(pub String);
+
+/// Where a player is in a conversation; each player holds their own.
+#[derive(Component, Debug)]
+pub struct Conversation {
+    pub npc: Entity,
+    pub dialogue: String,
+    pub node: String,
+}
+
+fn has_flag(world: &World, player: Entity, flag: &str) -> bool {
+    world
+        .get::<Flags>(player)
+        .is_some_and(|f| f.0.contains(flag))
+}
+
+fn meets(world: &mut World, player: Entity, zone: &str, cond: &ConditionDef) -> bool {
+    if !cond.race.is_empty()
+        && !world
+            .get::<Race>(player)
+            .is_some_and(|r| cond.race.contains(r))
+    {
+        return false;
+    }
+    if let Some(flag) = &cond.flag
+        && !has_flag(world, player, flag)
+    {
+        return false;
+    }
+    if let Some(flag) = &cond.not_flag
+        && has_flag(world, player, flag)
+    {
+        return false;
+    }
+    let quest_state = |world: &World, quest: &str| {
+        let quest = qualify(zone, quest);
+        world.get::<QuestLog>(player).map_or((false, false), |log| {
+            (log.is_active(&quest), log.completed.contains(&quest))
+        })
+    };
+    if let Some(quest) = &cond.quest_available
+        && quest_state(world, quest) != (false, false)
+    {
+        return false;
+    }
+    if let Some(quest) = &cond.quest_active
+        && !quest_state(world, quest).0
+    {
+        return false;
+    }
+    if let Some(quest) = &cond.quest_done
+        && !quest_state(world, quest).1
+    {
+        return false;
+    }
+    if let Some(keyword) = &cond.has_item
+        && !items_held_by(world, player)
+            .iter()
+            .any(|(_, item)| item.matches(keyword))
+    {
+        return false;
+    }
+    true
+}
+
+/// Options of `node` the player currently qualifies for
+fn available(world: &mut World, player: Entity, dialogue: &str, node: &NodeDef) -> Vec<OptionDef> {
+    let zone = zone_of(dialogue);
+    node.options
+        .iter()
+        .filter(|opt| meets(world, player, zone, &opt.conditions))
+        .cloned()
+        .collect()
+}
+
+/// Zone half of a "zone:id" registry key
+fn zone_of(id: &str) -> &str {
+    id.split_once(':').map_or(id, |(zone, _)| zone)
+}
+
+/// Shows a node's text and numbered options, ending the conversation if none apply
+fn present(world: &mut World, player: Entity, npc: Entity, dialogue: &str, node_id: &str) {
+    let node = world
+        .resource::<DialogueRegistry>()
+        .0
+        .get(dialogue)
+        .and_then(|d| d.node(node_id))
+        .cloned();
+    let Some(node) = node else {
+        world.entity_mut(player).remove::<Conversation>();
+        return;
+    };
+
+    let mut text = format!(
+        "{} says: {}",
+        capitalize(&display_name(world, npc)),
+        node.text
+    );
+    let options = available(world, player, dialogue, &node);
+    if options.is_empty() {
+        world.entity_mut(player).remove::<Conversation>();
+    } else {
+        for (i, opt) in options.iter().enumerate() {
+            text.push_str(&format!("\n  {}. {}", i + 1, opt.text));
+        }
+        text.push_str("\n(talk <number> to answer)");
+        world.entity_mut(player).insert(Conversation {
+            npc,
+            dialogue: dialogue.to_string(),
+            node: node.id,
+        });
+    }
+    reply(world, player, text);
+}
+
+fn perform(world: &mut World, player: Entity, npc: Entity, zone: &str, action: &DialogueAction) {
+    match action {
+        DialogueAction::SetFlag { flag } => {
+            if let Some(mut flags) = world.get_mut::<Flags>(player) {
+                flags.0.insert(flag.clone());
+            }
+        }
+        DialogueAction::ClearFlag { flag } => {
+            if let Some(mut flags) = world.get_mut::<Flags>(player) {
+                flags.0.remove(flag);
+            }
+        }
+        DialogueAction::GiveItem {
+            name,
+            keywords,
+            description,
+            value,
+            light,
+            flag,
+        } => {
+            if has_flag(world, player, flag) {
+                reply(
+                    world,
+                    player,
+                    format!(
+                        "{} has already given you {}.",
+                        capitalize(&display_name(world, npc)),
+                        name
+                    ),
+                );
+                return;
+            }
+            if let Some(mut flags) = world.get_mut::<Flags>(player) {
+                flags.0.insert(flag.clone());
+            }
+            let def = ItemDef {
+                name: name.clone(),
+                keywords: keywords.clone(),
+                description: description.clone(),
+                value: *value,
+                light: *light,
+            };
+            spawn_item(world, &def, player);
+            reply(
+                world,
+                player,
+                format!(
+                    "{} gives you {}.",
+                    capitalize(&display_name(world, npc)),
+                    name
+                ),
+            );
+        }
+        DialogueAction::TakeItem { keyword } => {
+            let found = items_held_by(world, player)
+                .into_iter()
+                .find(|(_, item)| item.matches(keyword));
+            if let Some((ent, item)) = found {
+                world.despawn(ent);
+                reply(world, player, format!("You hand over {}.", item.name));
+            }
+        }
+        DialogueAction::AcceptQuest { quest } => {
+            if !accept_quest(world, player, &qualify(zone, quest)) {
+                reply(world, player, "You can't take on that quest.".to_string());
+            }
+        }
+        DialogueAction::Emote { text } => {
+            if let Some(room) = world.get::<Location>(npc).map(|l| l.0) {
+                // Sent from the NPC, so the player hears it alongside everyone else.
+                world.write_message(BroadcastRoomEvent {
+                    from: npc,
+                    room,
+                    text: format!("{} {}", capitalize(&display_name(world, npc)), text),
+                    unseen: Some(format!("Someone {}", text)),
+                });
+            }
+        }
+    }
+}
+
+/// Opens a conversation with `npc` at its dialogue's starting node
+pub fn start_conversation(world: &mut World, player: Entity, npc: Entity) -> bool {
+    let Some(dialogue) = world.get::<Dialogue>(npc).map(|d| d.0.clone()) else {
+        return false;
+    };
+    let Some(start) = world
+        .resource::<DialogueRegistry>()
+        .0
+        .get(&dialogue)
+        .map(|d| d.start.clone())
+    else {
+        return false;
+    };
+    if let Some(template) = world.get::<Npc>(npc).map(|n| n.template.clone()) {
+        world.write_message(QuestEvent {
+            player,
+            trigger: QuestTrigger::Talk(template),
+        });
+    }
+    present(world, player, npc, &dialogue, &start);
+    true
+}
+
+/// Picks the player's `choice`th visible option in their current conversation
+pub fn choose_option(world: &mut World, player: Entity, choice: usize) {
+    let Some((npc, dialogue, node_id)) = world
+        .get::<Conversation>(player)
+        .map(|c| (c.npc, c.dialogue.clone(), c.node.clone()))
+    else {
+        reply(world, player, "You aren't talking to anyone.".to_string());
+        return;
+    };
+
+    let here = world.get::<Location>(player).map(|l| l.0);
+    if here.is_none() || world.get::<Location>(npc).map(|l| l.0) != here {
+        world.entity_mut(player).remove::<Conversation>();
+        reply(world, player, "They are no longer here.".to_string());
+        return;
+    }
+
+    let node = world
+        .resource::<DialogueRegistry>()
+        .0
+        .get(&dialogue)
+        .and_then(|d| d.node(&node_id))
+        .cloned();
+    let options = match node {
+        Some(node) => available(world, player, &dialogue, &node),
+        None => Vec::new(),
+    };
+    let Some(option) = choice.checked_sub(1).and_then(|i| options.get(i)) else {
+        reply(world, player, "That isn't one of the choices.".to_string());
+        return;
+    };
+
+    reply(world, player, format!("You say: {}", option.text));
+    for action in &option.actions {
+        perform(world, player, npc, zone_of(&dialogue), action);
+    }
+    match &option.next {
+        Some(next) => present(world, player, npc, &dialogue, next),
+        None => {
+            world.entity_mut(player).remove::<Conversation>();
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::core::util::now_secs;
+use crate::core::world::ItemDef;
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+/// Credits a freshly created character starts with.
+pub const STARTING_CREDITS: u64 = 100;
+/// Entries kept in memory for the `transactions` command.
+const RECENT_TRANSACTIONS: usize = 100;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct StockDef {
+    #[serde(flatten)]
+    pub item: ItemDef,
+    /// Units on the shelf after each zone reset.
+    #[serde(
+        default = "default_quantity",
+        skip_serializing_if = "is_default_quantity"
+    )]
+    pub quantity: u32,
+}
+
+fn default_quantity() -> u32 {
+    5
+}
+
+fn is_default_quantity(quantity: &u32) -> bool {
+    *quantity == default_quantity()
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ShopDef {
+    /// Multiplier on an item's value when players buy it.
+    #[serde(
+        default = "default_buy_markup",
+        skip_serializing_if = "is_default_buy_markup"
+    )]
+    pub buy_markup: f64,
+    /// Multiplier on an item's value when players sell it.
+    #[serde(
+        default = "default_sell_markup",
+        skip_serializing_if = "is_default_sell_markup"
+    )]
+    pub sell_markup: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stock: Vec<StockDef>,
+}
+
+fn default_buy_markup() -> f64 {
+    1.25
+}
+
+fn is_default_buy_markup(markup: &f64) -> bool {
+    *markup == default_buy_markup()
+}
+
+fn default_sell_markup() -> f64 {
+    0.5
+}
+
+fn is_default_sell_markup(markup: &f64) -> bool {
+    *markup == default_sell_markup()
+}
+
+/// A shopkeeper's wares and what is left of each.
+#[derive(Component, Debug)]
+pub struct Shop {
+    pub def: ShopDef,
+    pub remaining: Vec<u32>,
+}
+
+impl Shop {
+    pub fn new(def: ShopDef) -> Self {
+        let remaining = def.stock.iter().map(|s| s.quantity).collect();
+        Self { def, remaining }
+    }
+
+    pub fn restock(&mut self) {
+        for (left, stock) in self.remaining.iter_mut().zip(&self.def.stock) {
+            *left = stock.quantity;
+        }
+    }
+
+    /// What a player pays for an item worth `value`
+    pub fn buy_price(&self, value: u32) -> u64 {
+        (f64::from(value) * self.def.buy_markup).ceil() as u64
+    }
+
+    /// What a player is paid for an item worth `value`
+    pub fn sell_price(&self, value: u32) -> u64 {
+        (f64::from(value) * self.def.sell_markup).floor() as u64
+    }
+}
+
+/// Append-only audit trail of every purchase and sale.
+#[derive(Resource)]
+pub struct TransactionLog {
+    path: PathBuf,
+    pub recent: VecDeque<String>,
+}
+
+impl TransactionLog {
+    /// Opens the log at `path`, seeding `recent` from whatever it already holds
+    pub fn new(path: &str) -> Self {
+        let existing = fs::read_to_string(path).unwrap_or_default();
+        let lines: Vec<&str> = existing.lines().collect();
+        let recent = lines[lines.len().saturating_sub(RECENT_TRANSACTIONS)..]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        Self {
+            path: PathBuf::from(path),
+            recent,
+        }
+    }
+
+    pub fn record(&mut self, entry: String) {
+        let line = format!("{} {}", now_secs(), entry);
+
+        if let Some(dir) = self.path.parent()
+            && let Err(e) = fs::create_dir_all(dir)
+        {
+            error!("Failed to create {:?}: {}", dir, e);
+        }
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut f| writeln!(f, "{}", line));
+        if let Err(e) = written {
+            error!("Failed to write transaction log: {}", e);
+        }
+
+        if self.recent.len() == RECENT_TRANSACTIONS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shop(buy_markup: f64, sell_markup: f64) -> Shop {
+        Shop::new(ShopDef {
+            buy_markup,
+            sell_markup,
+            stock: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn buying_rounds_up() {
+        let shop = shop(1.25, 0.5);
+        assert_eq!(shop.buy_price(10), 13);
+        assert_eq!(shop.buy_price(8), 10);
+        assert_eq!(shop.buy_price(0), 0);
+    }
+
+    #[test]
+    fn selling_rounds_down() {
+        let shop = shop(1.25, 0.5);
+        assert_eq!(shop.sell_price(10), 5);
+        assert_eq!(shop.sell_price(7), 3);
+        assert_eq!(shop.sell_price(1), 0);
+    }
+
+    #[test]
+    fn selling_never_beats_buying_at_default_markups() {
+        let shop = shop(default_buy_markup(), default_sell_markup());
+        for value in 0..200 {
+            assert!(shop.sell_price(value) <= shop.buy_price(value));
+        }
+    }
+}
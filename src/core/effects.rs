@@ -0,0 +1,289 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::components::{Health, Race};
+use crate::core::events::{ApplyEffectEvent, DamageEvent, OutputEvent};
+use crate::core::race::RaceTrait;
+use crate::core::world::TICKS_PER_SECOND;
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EffectKind {
+    /// Seared by Xenomorph blood; damage each second per stack.
+    AcidBurn,
+    /// Too terrified to take aim.
+    Frightened,
+    /// Healing each second.
+    Regenerating,
+    /// Post-death frailty; outgoing damage is halved.
+    Weakened,
+}
+
+/// What happens when an effect is applied to someone who already has it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stacking {
+    /// Reset the remaining duration.
+    Refresh,
+    /// Add the new duration onto what is left.
+    Extend,
+    /// Refresh and add a stack, up to `max`.
+    Intensify { max: u32 },
+}
+
+impl EffectKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EffectKind::AcidBurn => "Acid burn",
+            EffectKind::Frightened => "Frightened",
+            EffectKind::Regenerating => "Regenerating",
+            EffectKind::Weakened => "Weakened",
+        }
+    }
+
+    pub fn stacking(&self) -> Stacking {
+        match self {
+            EffectKind::AcidBurn => Stacking::Intensify { max: 3 },
+            EffectKind::Frightened => Stacking::Refresh,
+            EffectKind::Regenerating => Stacking::Extend,
+            EffectKind::Weakened => Stacking::Refresh,
+        }
+    }
+
+    /// Ticks between periodic callbacks, for effects that have one
+    pub fn interval(&self) -> Option<u64> {
+        match self {
+            EffectKind::AcidBurn | EffectKind::Regenerating => Some(TICKS_PER_SECOND),
+            EffectKind::Frightened | EffectKind::Weakened => None,
+        }
+    }
+
+    pub fn apply_message(&self) -> &'static str {
+        match self {
+            EffectKind::AcidBurn => "The acid eats into your flesh!",
+            EffectKind::Frightened => "Terror grips you!",
+            EffectKind::Regenerating => "A soothing warmth spreads through you.",
+            EffectKind::Weakened => "Your strength will take a while to return.",
+        }
+    }
+
+    pub fn expire_message(&self) -> &'static str {
+        match self {
+            EffectKind::AcidBurn => "The burning subsides.",
+            EffectKind::Frightened => "You regain your composure.",
+            EffectKind::Regenerating => "The warmth fades.",
+            EffectKind::Weakened => "You feel your strength return.",
+        }
+    }
+
+    /// Whether `race` shrugs the effect off entirely
+    pub fn resisted_by(&self, race: Race) -> bool {
+        match self {
+            EffectKind::AcidBurn => race.has_trait(RaceTrait::AcidBlood),
+            EffectKind::Frightened => race.has_trait(RaceTrait::FearImmune),
+            EffectKind::Regenerating | EffectKind::Weakened => false,
+        }
+    }
+}
+
+/// An effect in progress; saved with the character so relogging doesn't shake it off.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ActiveEffect {
+    pub kind: EffectKind,
+    pub remaining: u64,
+    pub stacks: u32,
+    /// Per-stack strength of the periodic callback.
+    pub magnitude: u32,
+    #[serde(skip)]
+    pub source: Option<Entity>,
+}
+
+#[derive(Component, Debug, Default)]
+pub struct Effects(pub Vec<ActiveEffect>);
+
+impl Effects {
+    pub fn get(&self, kind: EffectKind) -> Option<&ActiveEffect> {
+        self.0.iter().find(|e| e.kind == kind)
+    }
+
+    pub fn has(&self, kind: EffectKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    /// Adds `fresh`, or folds it into an existing effect of the same kind by its stacking rule
+    pub fn add(&mut self, fresh: ActiveEffect) {
+        let Some(existing) = self.0.iter_mut().find(|e| e.kind == fresh.kind) else {
+            self.0.push(fresh);
+            return;
+        };
+        match fresh.kind.stacking() {
+            Stacking::Refresh => existing.remaining = fresh.remaining,
+            Stacking::Extend => existing.remaining += fresh.remaining,
+            Stacking::Intensify { max } => {
+                existing.remaining = fresh.remaining;
+                existing.stacks = (existing.stacks + 1).min(max);
+            }
+        }
+        existing.source = fresh.source;
+    }
+}
+
+pub fn has_effect(world: &World, ent: Entity, kind: EffectKind) -> bool {
+    world.get::<Effects>(ent).is_some_and(|e| e.has(kind))
+}
+
+/// Applies requested effects, honouring each kind's stacking rule
+pub fn apply_effects(
+    mut commands: Commands,
+    mut events: MessageReader<ApplyEffectEvent>,
+    mut targets: Query<(Option<&mut Effects>, Option<&Race>)>,
+    mut output: MessageWriter<OutputEvent>,
+) {
+    for event in events.read() {
+        let Ok((effects, race)) = targets.get_mut(event.target) else {
+            continue;
+        };
+        if race.is_some_and(|r| event.kind.resisted_by(*r)) {
+            continue;
+        }
+
+        let fresh = ActiveEffect {
+            kind: event.kind,
+            remaining: event.duration,
+            stacks: 1,
+            magnitude: event.magnitude,
+            source: event.source,
+        };
+        match effects {
+            Some(mut effects) => effects.add(fresh),
+            None => {
+                commands.entity(event.target).insert(Effects(vec![fresh]));
+            }
+        }
+
+        output.write(OutputEvent {
+            player: event.target,
+            text: event.kind.apply_message().to_string(),
+        });
+    }
+}
+
+/// Counts every effect down by one tick, firing periodic callbacks and expiring the spent
+pub fn tick_effects(
+    mut query: Query<(Entity, &mut Effects, Option<&mut Health>)>,
+    mut damage: MessageWriter<DamageEvent>,
+    mut output: MessageWriter<OutputEvent>,
+) {
+    for (ent, mut effects, mut health) in query.iter_mut() {
+        for effect in effects.0.iter_mut() {
+            effect.remaining = effect.remaining.saturating_sub(1);
+            let Some(interval) = effect.kind.interval() else {
+                continue;
+            };
+            if !effect.remaining.is_multiple_of(interval) {
+                continue;
+            }
+
+            let amount = effect.magnitude * effect.stacks;
+            match effect.kind {
+                EffectKind::AcidBurn => {
+                    damage.write(DamageEvent {
+                        source: effect.source,
+                        target: ent,
+                        amount,
+                    });
+                }
+                EffectKind::Regenerating => {
+                    if let Some(health) = health.as_mut()
+                        && health.current > 0
+                    {
+                        health.current = (health.current + amount).min(health.max);
+                    }
+                }
+                EffectKind::Frightened | EffectKind::Weakened => {}
+            }
+        }
+
+        effects.0.retain(|effect| {
+            if effect.remaining == 0 {
+                output.write(OutputEvent {
+                    player: ent,
+                    text: effect.kind.expire_message().to_string(),
+                });
+                return false;
+            }
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effect(kind: EffectKind, remaining: u64) -> ActiveEffect {
+        ActiveEffect {
+            kind,
+            remaining,
+            stacks: 1,
+            magnitude: 1,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn refresh_resets_duration() {
+        let mut effects = Effects::default();
+        effects.add(effect(EffectKind::Frightened, 100));
+        effects.0[0].remaining = 10;
+        effects.add(effect(EffectKind::Frightened, 50));
+        assert_eq!(effects.0.len(), 1);
+        assert_eq!(effects.0[0].remaining, 50);
+        assert_eq!(effects.0[0].stacks, 1);
+    }
+
+    #[test]
+    fn extend_adds_duration() {
+        let mut effects = Effects::default();
+        effects.add(effect(EffectKind::Regenerating, 100));
+        effects.add(effect(EffectKind::Regenerating, 40));
+        assert_eq!(effects.0[0].remaining, 140);
+        assert_eq!(effects.0[0].stacks, 1);
+    }
+
+    #[test]
+    fn intensify_stacks_up_to_max() {
+        let mut effects = Effects::default();
+        for _ in 0..5 {
+            effects.add(effect(EffectKind::AcidBurn, 60));
+        }
+        assert_eq!(effects.0.len(), 1);
+        assert_eq!(effects.0[0].stacks, 3);
+        assert_eq!(effects.0[0].remaining, 60);
+    }
+
+    #[test]
+    fn different_kinds_coexist() {
+        let mut effects = Effects::default();
+        effects.add(effect(EffectKind::AcidBurn, 60));
+        effects.add(effect(EffectKind::Weakened, 60));
+        assert!(effects.has(EffectKind::AcidBurn));
+        assert!(effects.has(EffectKind::Weakened));
+        assert!(!effects.has(EffectKind::Frightened));
+    }
+}
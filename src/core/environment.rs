@@ -0,0 +1,118 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::combat::Fighting;
+use crate::core::commands::reply;
+use crate::core::components::{Health, Location, Player, Race, RoomFlag, RoomFlags};
+use crate::core::events::{DamageEvent, DeathEvent, OutputEvent};
+use crate::core::race::RaceTrait;
+use crate::core::world::{GameTick, TICKS_PER_SECOND, has_flag};
+
+use bevy_ecs::prelude::*;
+
+/// Ticks between bouts of vacuum or hazard damage.
+const HAZARD_TICKS: u64 = 3 * TICKS_PER_SECOND;
+const HAZARD_DAMAGE: u32 = 6;
+
+/// Hurts players standing in vacuum or hazardous rooms, unless their race is immune
+pub fn environmental_damage(
+    tick: Res<GameTick>,
+    victims: Query<(Entity, &Location, &Health, Option<&Race>), With<Player>>,
+    rooms: Query<&RoomFlags>,
+    mut damage: MessageWriter<DamageEvent>,
+    mut output: MessageWriter<OutputEvent>,
+) {
+    if !tick.0.is_multiple_of(HAZARD_TICKS) {
+        return;
+    }
+    for (ent, loc, health, race) in victims.iter() {
+        let Ok(flags) = rooms.get(loc.0) else {
+            continue;
+        };
+        if health.current == 0 {
+            continue;
+        }
+        let immune = |t: RaceTrait| race.is_some_and(|r| r.has_trait(t));
+        let mut hurt = |text: &str| {
+            output.write(OutputEvent {
+                player: ent,
+                text: text.to_string(),
+            });
+            damage.write(DamageEvent {
+                source: None,
+                target: ent,
+                amount: HAZARD_DAMAGE,
+            });
+        };
+        if flags.has(RoomFlag::Vacuum) && !immune(RaceTrait::VacuumImmune) {
+            hurt("There is no air here! Your lungs strain and burn.");
+        }
+        if flags.has(RoomFlag::Hazard) && !immune(RaceTrait::HazardImmune) {
+            hurt("The poisoned air sears your skin and throat.");
+        }
+    }
+}
+
+/// Breaks off any fight that has spilled into a safe room
+pub fn disengage_in_safe_rooms(
+    mut commands: Commands,
+    fighters: Query<(Entity, &Location), With<Fighting>>,
+    rooms: Query<&RoomFlags>,
+) {
+    for (ent, loc) in fighters.iter() {
+        if rooms.get(loc.0).is_ok_and(|f| f.has(RoomFlag::Safe)) {
+            commands.entity(ent).remove::<Fighting>();
+        }
+    }
+}
+
+/// Kills a character who has just walked into a death trap; true if it was sprung
+pub fn spring_death_trap(world: &mut World, victim: Entity, room: Entity) -> bool {
+    if !has_flag(world, room, RoomFlag::DeathTrap) {
+        return false;
+    }
+    // Killed outright, so nothing queued ahead of the damage can heal them out of it.
+    let Some(mut health) = world.get_mut::<Health>(victim) else {
+        return false;
+    };
+    if health.current == 0 {
+        return true;
+    }
+    health.current = 0;
+    reply(
+        world,
+        victim,
+        "You have blundered into certain death!".to_string(),
+    );
+    world.write_message(DeathEvent {
+        victim,
+        killer: None,
+    });
+    true
+}
+
+/// Whether fighting is forbidden in `room`, telling `player` so if it is
+pub fn forbid_violence(world: &mut World, player: Entity, room: Entity) -> bool {
+    if !has_flag(world, room, RoomFlag::Safe) {
+        return false;
+    }
+    reply(
+        world,
+        player,
+        "This is a safe place; violence is forbidden here.".to_string(),
+    );
+    true
+}
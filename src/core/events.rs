@@ -14,8 +14,22 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::core::channels::ChannelId;
+use crate::core::effects::EffectKind;
+use crate::core::quests::QuestTrigger;
+
 use bevy_ecs::prelude::*;
 
+#[derive(Message)]
+pub struct ApplyEffectEvent {
+    pub target: Entity,
+    pub kind: EffectKind,
+    /// Duration in ticks.
+    pub duration: u64,
+    pub magnitude: u32,
+    pub source: Option<Entity>,
+}
+
 #[derive(Message)]
 pub struct BroadcastEvent {
     pub from: Entity,
@@ -27,6 +41,8 @@ pub struct BroadcastRoomEvent {
     pub from: Entity,
     pub room: Entity,
     pub text: String,
+    /// What listeners who can't see `from` get instead; `None` when `text` doesn't give them away.
+    pub unseen: Option<String>,
 }
 
 #[derive(Message)]
@@ -36,19 +52,69 @@ pub struct BroadcastZoneEvent {
     pub text: String,
 }
 
+/// A line spoken on a chat channel, delivered to its subscribers.
+#[derive(Message)]
+pub struct ChannelEvent {
+    pub from: Entity,
+    pub channel: ChannelId,
+    pub message: String,
+}
+
 #[derive(Message)]
 pub struct CommandEvent {
     pub player: Entity,
     pub input: String,
 }
 
+#[derive(Message)]
+pub struct DamageEvent {
+    pub source: Option<Entity>,
+    pub target: Entity,
+    pub amount: u32,
+}
+
+#[derive(Message)]
+pub struct DeathEvent {
+    pub victim: Entity,
+    pub killer: Option<Entity>,
+}
+
 #[derive(Message)]
 pub struct DisconnectEvent {
     pub player: Entity,
 }
 
+/// Room-visible action text, shown to everyone present except the actor and any target.
+#[derive(Message)]
+pub struct EmoteEvent {
+    pub from: Entity,
+    pub target: Option<Entity>,
+    pub room: Entity,
+    pub text: String,
+    /// What listeners who can't see `from` get instead; `None` when `text` doesn't give them away.
+    pub unseen: Option<String>,
+}
+
+#[derive(Message)]
+pub struct ExperienceEvent {
+    pub player: Entity,
+    pub amount: u64,
+}
+
 #[derive(Message)]
 pub struct OutputEvent {
     pub player: Entity,
     pub text: String,
 }
+
+/// Something happened that quest objectives may be waiting on.
+#[derive(Message)]
+pub struct QuestEvent {
+    pub player: Entity,
+    pub trigger: QuestTrigger,
+}
+
+#[derive(Message)]
+pub struct SaveEvent {
+    pub player: Entity,
+}
@@ -0,0 +1,259 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::reply;
+use crate::core::components::{HeldBy, Item, Location};
+use crate::core::events::ExperienceEvent;
+use crate::core::text::display_name;
+
+use bevy_ecs::prelude::*;
+
+/// Most players a single group may hold, leader included.
+pub const MAX_GROUP_SIZE: usize = 6;
+/// Extra experience per additional member sharing a kill, in percent.
+const GROUP_XP_BONUS: u64 = 10;
+
+/// Trailing another character; moves through `cmd_move` drag the follower along.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Following(pub Entity);
+
+/// Points at the leader of this character's group. The leader points at itself.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GroupMember(pub Entity);
+
+/// Group settings, kept on the leader.
+#[derive(Component, Debug, Default)]
+pub struct GroupLeader {
+    pub loot: LootRule,
+    /// Next member in line under round robin.
+    pub next: usize,
+}
+
+/// Invitation to join the group led by this entity.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GroupInvite(pub Entity);
+
+/// Who gets the belongings of an NPC the group kills
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LootRule {
+    /// Everything drops into the corpse for anyone to take.
+    #[default]
+    Free,
+    /// Items are handed out one at a time to each member present in turn.
+    RoundRobin,
+    /// Everything goes to the leader, if present.
+    Leader,
+}
+
+impl LootRule {
+    pub fn parse(s: &str) -> Option<LootRule> {
+        match s {
+            "free" => Some(LootRule::Free),
+            "roundrobin" | "round" | "rr" => Some(LootRule::RoundRobin),
+            "leader" => Some(LootRule::Leader),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LootRule::Free => "free for all",
+            LootRule::RoundRobin => "round robin",
+            LootRule::Leader => "leader takes all",
+        }
+    }
+}
+
+/// Leader of the group `ent` belongs to, if any
+pub fn leader_of(world: &World, ent: Entity) -> Option<Entity> {
+    world.get::<GroupMember>(ent).map(|m| m.0)
+}
+
+/// Everyone in the group led by `leader`, leader first
+pub fn members(world: &mut World, leader: Entity) -> Vec<Entity> {
+    let mut rest: Vec<Entity> = world
+        .query::<(Entity, &GroupMember)>()
+        .iter(world)
+        .filter(|(ent, m)| m.0 == leader && *ent != leader)
+        .map(|(ent, _)| ent)
+        .collect();
+    rest.sort();
+    let mut all = vec![leader];
+    all.extend(rest);
+    all
+}
+
+/// Sends `text` to every member of `ent`'s group
+pub fn tell_group(world: &mut World, ent: Entity, text: &str) {
+    let Some(leader) = leader_of(world, ent) else {
+        return;
+    };
+    for player in members(world, leader) {
+        reply(world, player, text.to_string());
+    }
+}
+
+/// Members of `ent`'s group standing in `room`; just `ent` when it isn't grouped
+fn present(world: &mut World, ent: Entity, room: Entity) -> Vec<Entity> {
+    let Some(leader) = leader_of(world, ent) else {
+        return vec![ent];
+    };
+    members(world, leader)
+        .into_iter()
+        .filter(|m| world.get::<Location>(*m).is_some_and(|l| l.0 == room))
+        .collect()
+}
+
+/// Splits experience earned in `room` between the earner's group mates there,
+/// with a small bonus for each extra member
+pub fn share_experience(world: &mut World, earner: Entity, room: Entity, amount: u64) {
+    let sharers = present(world, earner, room);
+    let count = sharers.len().max(1) as u64;
+    let total = amount + amount * GROUP_XP_BONUS * (count - 1) / 100;
+    let share = total.div_ceil(count);
+    for player in sharers {
+        world.write_message(ExperienceEvent {
+            player,
+            amount: share,
+        });
+    }
+}
+
+/// Hands `items` out under the killer's group loot rule.
+/// Returns false when the rule leaves them in the corpse.
+pub fn distribute_loot(world: &mut World, killer: Entity, room: Entity, items: &[Entity]) -> bool {
+    let Some(leader) = leader_of(world, killer) else {
+        return false;
+    };
+    let Some(rule) = world.get::<GroupLeader>(leader).map(|g| g.loot) else {
+        return false;
+    };
+    let sharers = present(world, killer, room);
+    let recipients: Vec<Entity> = match rule {
+        LootRule::Free => return false,
+        LootRule::Leader if sharers.contains(&leader) => vec![leader],
+        LootRule::Leader => return false,
+        LootRule::RoundRobin => sharers,
+    };
+    if recipients.is_empty() {
+        return false;
+    }
+
+    let mut next = world.get::<GroupLeader>(leader).map_or(0, |g| g.next);
+    for &item in items {
+        let recipient = recipients[next % recipients.len()];
+        next += 1;
+        world.entity_mut(item).insert(HeldBy(recipient));
+        let item_name = world
+            .get::<Item>(item)
+            .map_or("something".to_string(), |i| i.name.clone());
+        let name = display_name(world, recipient);
+        for player in members(world, leader) {
+            let text = if player == recipient {
+                format!("You receive {}.", item_name)
+            } else {
+                format!("{} receives {}.", name, item_name)
+            };
+            reply(world, player, text);
+        }
+    }
+    if let Some(mut group) = world.get_mut::<GroupLeader>(leader) {
+        group.next = next % recipients.len();
+    }
+    true
+}
+
+/// Makes `ent` stop following whoever it trails
+pub fn stop_following(world: &mut World, ent: Entity) {
+    if let Some(Following(target)) = world.entity_mut(ent).take::<Following>() {
+        let name = display_name(world, target);
+        reply(world, ent, format!("You stop following {}.", name));
+    }
+}
+
+/// Takes `ent` out of its group, handing leadership on or disbanding as needed
+pub fn leave_group(world: &mut World, ent: Entity) {
+    let Some(leader) = leader_of(world, ent) else {
+        return;
+    };
+    let name = display_name(world, ent);
+    let rest: Vec<Entity> = members(world, leader)
+        .into_iter()
+        .filter(|m| *m != ent)
+        .collect();
+    let settings = if ent == leader {
+        world.entity_mut(ent).take::<GroupLeader>()
+    } else {
+        None
+    };
+    world.entity_mut(ent).remove::<GroupMember>();
+    reply(world, ent, "You leave the group.".to_string());
+
+    if rest.len() < 2 {
+        for player in rest {
+            world
+                .entity_mut(player)
+                .remove::<(GroupMember, GroupLeader)>();
+            reply(
+                world,
+                player,
+                format!("{} has left the group. Your group has disbanded.", name),
+            );
+        }
+        return;
+    }
+
+    let new_leader = if ent == leader { rest[0] } else { leader };
+    if let Some(settings) = settings {
+        world.entity_mut(new_leader).insert(settings);
+    }
+    let new_name = display_name(world, new_leader);
+    for &player in &rest {
+        world.entity_mut(player).insert(GroupMember(new_leader));
+        let text = if new_leader == leader {
+            format!("{} has left the group.", name)
+        } else if player == new_leader {
+            format!("{} has left the group. You now lead it.", name)
+        } else {
+            format!("{} has left the group. {} now leads it.", name, new_name)
+        };
+        reply(world, player, text);
+    }
+}
+
+/// Cuts every tie to `ent` before it leaves the world: its followers, its group and its invitations
+pub fn part_company(world: &mut World, ent: Entity) {
+    let followers: Vec<Entity> = world
+        .query::<(Entity, &Following)>()
+        .iter(world)
+        .filter(|(_, f)| f.0 == ent)
+        .map(|(follower, _)| follower)
+        .collect();
+    for follower in followers {
+        stop_following(world, follower);
+    }
+    let invited: Vec<Entity> = world
+        .query::<(Entity, &GroupInvite)>()
+        .iter(world)
+        .filter(|(_, i)| i.0 == ent)
+        .map(|(invitee, _)| invitee)
+        .collect();
+    for invitee in invited {
+        world.entity_mut(invitee).remove::<GroupInvite>();
+    }
+    world.entity_mut(ent).remove::<Following>();
+    leave_group(world, ent);
+}
@@ -0,0 +1,375 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::reply;
+use crate::core::mail::{pin_post, send_letter};
+use crate::core::olc::set_description;
+
+use bevy_ecs::prelude::*;
+
+/// Lines shown per page before the pager waits for the player.
+pub const PAGE_LINES: usize = 20;
+
+const EDITOR_HELP: &str =
+    "Editor commands: .s save, .q abort, .l list, .d <n> delete line, .c clear, .h help";
+
+/// What a finished editor buffer is for.
+#[derive(Debug, Clone)]
+pub enum EditTarget {
+    Mail { to: String, subject: String },
+    Post { board: String, subject: String },
+    RoomDescription { room: Entity },
+}
+
+#[derive(Debug)]
+pub struct Editor {
+    pub target: EditTarget,
+    pub lines: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct Pager {
+    pub lines: Vec<String>,
+    pub offset: usize,
+}
+
+/// What to do when a prompt is answered yes.
+#[derive(Debug, Clone, Copy)]
+pub enum PromptAction {
+    /// Throw away the editor beneath the prompt.
+    DiscardEdit,
+}
+
+#[derive(Debug)]
+pub struct Prompt {
+    pub action: PromptAction,
+}
+
+/// How a player's typed lines are interpreted.
+#[derive(Debug)]
+pub enum InputMode {
+    /// Lines are commands.
+    Normal,
+    Editor(Editor),
+    Pager(Pager),
+    Prompt(Prompt),
+}
+
+/// A player's input modes; the top one receives every line, and `Normal` is always at the bottom.
+#[derive(Component, Debug)]
+pub struct InputModes(pub Vec<InputMode>);
+
+impl Default for InputModes {
+    fn default() -> Self {
+        Self(vec![InputMode::Normal])
+    }
+}
+
+impl InputModes {
+    /// Whether lines should go to the command dispatcher
+    pub fn is_normal(&self) -> bool {
+        matches!(self.0.last(), None | Some(InputMode::Normal))
+    }
+}
+
+fn push_mode(world: &mut World, player: Entity, mode: InputMode) {
+    if let Some(mut modes) = world.get_mut::<InputModes>(player) {
+        modes.0.push(mode);
+    }
+}
+
+/// Opens the line editor on top of whatever the player is doing
+pub fn open_editor(world: &mut World, player: Entity, target: EditTarget, lines: Vec<String>) {
+    let count = lines.len();
+    push_mode(world, player, InputMode::Editor(Editor { target, lines }));
+    let text = if count == 0 {
+        format!("Enter your text. {}", EDITOR_HELP)
+    } else {
+        format!(
+            "Editing {} existing line{}. {}",
+            count,
+            if count == 1 { "" } else { "s" },
+            EDITOR_HELP
+        )
+    };
+    reply(world, player, text);
+}
+
+/// Shows text a page at a time, or all at once when it's short
+pub fn page(world: &mut World, player: Entity, text: String) {
+    let lines: Vec<String> = text.lines().map(str::to_string).collect();
+    if lines.len() <= PAGE_LINES || world.get::<InputModes>(player).is_none() {
+        reply(world, player, text);
+        return;
+    }
+    let mut pager = Pager { lines, offset: 0 };
+    let shown = next_page(&mut pager);
+    push_mode(world, player, InputMode::Pager(pager));
+    reply(world, player, shown);
+}
+
+/// Renders the pager's next page and advances it
+fn next_page(pager: &mut Pager) -> String {
+    let end = (pager.offset + PAGE_LINES).min(pager.lines.len());
+    let mut text = pager.lines[pager.offset..end].join("\n");
+    pager.offset = end;
+    if !finished(pager) {
+        text.push_str(&format!(
+            "\n-- More ({}/{}) -- [Enter] to continue, 'q' to stop",
+            end,
+            pager.lines.len()
+        ));
+    }
+    text
+}
+
+fn finished(pager: &Pager) -> bool {
+    pager.offset >= pager.lines.len()
+}
+
+/// Hands a line to the player's current input mode, returning false if it's a command
+pub fn route_line(world: &mut World, player: Entity, line: &str) -> bool {
+    let Some(mode) = world
+        .get_mut::<InputModes>(player)
+        .and_then(|mut m| if m.is_normal() { None } else { m.0.pop() })
+    else {
+        return false;
+    };
+
+    match mode {
+        InputMode::Normal => push_mode(world, player, InputMode::Normal),
+        InputMode::Editor(editor) => edit(world, player, editor, line),
+        InputMode::Pager(mut pager) => {
+            if !line.trim().eq_ignore_ascii_case("q") {
+                let shown = next_page(&mut pager);
+                if !finished(&pager) {
+                    push_mode(world, player, InputMode::Pager(pager));
+                }
+                reply(world, player, shown);
+            } else {
+                reply(world, player, "Done reading.".to_string());
+            }
+        }
+        InputMode::Prompt(prompt) => answer(world, player, prompt, line),
+    }
+    true
+}
+
+fn edit(world: &mut World, player: Entity, mut editor: Editor, line: &str) {
+    let trimmed = line.trim();
+    let mut words = trimmed.split_whitespace();
+    let command = if trimmed.starts_with('.') && trimmed.len() > 1 {
+        words.next()
+    } else {
+        None
+    };
+
+    let text = match command {
+        None => {
+            editor.lines.push(line.to_string());
+            push_mode(world, player, InputMode::Editor(editor));
+            return;
+        }
+        Some(".s") => {
+            if editor.lines.is_empty() {
+                reply(world, player, "Nothing written; editor closed.".to_string());
+            } else {
+                save(world, player, editor.target, editor.lines);
+            }
+            return;
+        }
+        Some(".q") => {
+            if editor.lines.is_empty() {
+                reply(world, player, "Editing aborted.".to_string());
+                return;
+            }
+            let count = editor.lines.len();
+            push_mode(world, player, InputMode::Editor(editor));
+            push_mode(
+                world,
+                player,
+                InputMode::Prompt(Prompt {
+                    action: PromptAction::DiscardEdit,
+                }),
+            );
+            reply(
+                world,
+                player,
+                format!(
+                    "Discard {} line{}? (y/n)",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ),
+            );
+            return;
+        }
+        Some(".l") => {
+            if editor.lines.is_empty() {
+                "The buffer is empty.".to_string()
+            } else {
+                editor
+                    .lines
+                    .iter()
+                    .enumerate()
+                    .map(|(i, l)| format!("{:>3}: {}", i + 1, l))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        Some(".d") => match words
+            .next()
+            .and_then(|n| n.parse::<usize>().ok())
+            .filter(|n| (1..=editor.lines.len()).contains(n))
+        {
+            Some(n) => {
+                editor.lines.remove(n - 1);
+                format!("Deleted line {}.", n)
+            }
+            None => "Usage: .d <line number>".to_string(),
+        },
+        Some(".c") => {
+            editor.lines.clear();
+            "Buffer cleared.".to_string()
+        }
+        Some(".h") => EDITOR_HELP.to_string(),
+        Some(other) => format!("Unknown editor command {}. {}", other, EDITOR_HELP),
+    };
+    push_mode(world, player, InputMode::Editor(editor));
+    reply(world, player, text);
+}
+
+fn answer(world: &mut World, player: Entity, prompt: Prompt, line: &str) {
+    let yes = matches!(line.trim().to_lowercase().as_str(), "y" | "yes");
+    match prompt.action {
+        PromptAction::DiscardEdit => {
+            if yes {
+                if let Some(mut modes) = world.get_mut::<InputModes>(player)
+                    && matches!(modes.0.last(), Some(InputMode::Editor(_)))
+                {
+                    modes.0.pop();
+                }
+                reply(world, player, "Editing aborted.".to_string());
+            } else {
+                reply(world, player, "Back to editing.".to_string());
+            }
+        }
+    }
+}
+
+/// Delivers a finished buffer to whatever it was written for
+fn save(world: &mut World, player: Entity, target: EditTarget, lines: Vec<String>) {
+    match target {
+        EditTarget::Mail { to, subject } => send_letter(world, player, &to, subject, lines),
+        EditTarget::Post { board, subject } => pin_post(world, player, &board, subject, lines),
+        EditTarget::RoomDescription { room } => set_description(world, player, room, lines),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OutputEvent;
+
+    fn setup() -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<Messages<OutputEvent>>();
+        let room = world.spawn_empty().id();
+        let player = world.spawn(InputModes::default()).id();
+        open_editor(
+            &mut world,
+            player,
+            EditTarget::RoomDescription { room },
+            Vec::new(),
+        );
+        (world, player)
+    }
+
+    fn buffer(world: &World, player: Entity) -> Option<Vec<String>> {
+        match world.get::<InputModes>(player)?.0.last()? {
+            InputMode::Editor(editor) => Some(editor.lines.clone()),
+            _ => None,
+        }
+    }
+
+    fn last_output(world: &mut World) -> String {
+        world
+            .resource_mut::<Messages<OutputEvent>>()
+            .drain()
+            .last()
+            .map(|e| e.text)
+            .unwrap_or_default()
+    }
+
+    fn type_lines(world: &mut World, player: Entity, lines: &[&str]) {
+        for line in lines {
+            assert!(route_line(world, player, line));
+        }
+    }
+
+    #[test]
+    fn plain_lines_fill_the_buffer() {
+        let (mut world, player) = setup();
+        type_lines(&mut world, player, &["first", "second", ".nothing"]);
+        assert_eq!(
+            buffer(&world, player),
+            Some(vec!["first".to_string(), "second".to_string()])
+        );
+        assert!(last_output(&mut world).starts_with("Unknown editor command .nothing"));
+    }
+
+    #[test]
+    fn list_delete_and_clear() {
+        let (mut world, player) = setup();
+        type_lines(&mut world, player, &["one", "two", "three", ".l"]);
+        assert_eq!(last_output(&mut world), "  1: one\n  2: two\n  3: three");
+
+        type_lines(&mut world, player, &[".d 2"]);
+        assert_eq!(
+            buffer(&world, player),
+            Some(vec!["one".to_string(), "three".to_string()])
+        );
+        type_lines(&mut world, player, &[".d 9"]);
+        assert_eq!(last_output(&mut world), "Usage: .d <line number>");
+
+        type_lines(&mut world, player, &[".c"]);
+        assert_eq!(buffer(&world, player), Some(Vec::new()));
+    }
+
+    #[test]
+    fn quitting_asks_before_discarding() {
+        let (mut world, player) = setup();
+        type_lines(&mut world, player, &["draft", ".q"]);
+        assert!(matches!(
+            world.get::<InputModes>(player).unwrap().0.last(),
+            Some(InputMode::Prompt(_))
+        ));
+
+        type_lines(&mut world, player, &["n"]);
+        assert_eq!(buffer(&world, player), Some(vec!["draft".to_string()]));
+
+        type_lines(&mut world, player, &[".q", "y"]);
+        assert!(world.get::<InputModes>(player).unwrap().is_normal());
+        assert!(!route_line(&mut world, player, "look"));
+    }
+
+    #[test]
+    fn saving_an_empty_buffer_closes_the_editor() {
+        let (mut world, player) = setup();
+        type_lines(&mut world, player, &[".s"]);
+        assert!(world.get::<InputModes>(player).unwrap().is_normal());
+        assert_eq!(last_output(&mut world), "Nothing written; editor closed.");
+    }
+}
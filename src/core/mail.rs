@@ -0,0 +1,199 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::reply;
+use crate::core::components::Name;
+use crate::core::storage::{BoardStore, MailStore, PlayerStore};
+use crate::core::text::{capitalize, display_name};
+use crate::core::util::now_secs;
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+/// A message waiting in someone's mailbox.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Letter {
+    pub from: String,
+    pub subject: String,
+    pub body: Vec<String>,
+    /// Unix time, in seconds.
+    pub sent: u64,
+    #[serde(default)]
+    pub read: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Mailbox {
+    #[serde(default)]
+    pub letters: Vec<Letter>,
+}
+
+impl Mailbox {
+    pub fn unread(&self) -> usize {
+        self.letters.iter().filter(|l| !l.read).count()
+    }
+}
+
+/// A note pinned to a bulletin board.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Post {
+    pub author: String,
+    pub subject: String,
+    pub body: Vec<String>,
+    /// Unix time, in seconds.
+    pub posted: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct BoardPosts {
+    #[serde(default)]
+    pub posts: Vec<Post>,
+}
+
+/// A bulletin board fixed in a room, as written in the zone file.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BoardDef {
+    #[serde(default = "default_board_name")]
+    pub name: String,
+    #[serde(default = "default_board_description")]
+    pub description: String,
+}
+
+fn default_board_name() -> String {
+    "a bulletin board".to_string()
+}
+
+fn default_board_description() -> String {
+    "A bulletin board is mounted on the wall here.".to_string()
+}
+
+/// A bulletin board; posts are stored under the qualified id of its room.
+#[derive(Component, Debug, Clone)]
+pub struct Board {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+}
+
+/// Sends a finished letter, reporting the outcome to its author
+pub fn send_letter(
+    world: &mut World,
+    player: Entity,
+    to: &str,
+    subject: String,
+    body: Vec<String>,
+) {
+    let from = display_name(world, player);
+    let letter = Letter {
+        from,
+        subject,
+        body,
+        sent: now_secs(),
+        read: false,
+    };
+    let text = match deliver_letter(world, to, letter) {
+        Ok(()) => format!("Your letter to {} has been sent.", capitalize(to)),
+        Err(e) => {
+            error!("Failed to deliver mail to {}: {:?}", to, e);
+            "The mail system is unavailable right now.".to_string()
+        }
+    };
+    reply(world, player, text);
+}
+
+/// Pins a finished note to a board, reporting the outcome to its author
+pub fn pin_post(
+    world: &mut World,
+    player: Entity,
+    board: &str,
+    subject: String,
+    body: Vec<String>,
+) {
+    let author = display_name(world, player);
+    let post = Post {
+        author,
+        subject,
+        body,
+        posted: now_secs(),
+    };
+    let store = world.resource::<BoardStore>();
+    let posted = store.load(board).and_then(|mut posts| {
+        posts.posts.push(post);
+        store.save(board, &posts)
+    });
+    let text = match posted {
+        Ok(()) => "Your note has been pinned to the board.".to_string(),
+        Err(e) => {
+            error!("Failed to post to board {}: {:?}", board, e);
+            "The board won't take your note right now.".to_string()
+        }
+    };
+    reply(world, player, text);
+}
+
+/// Files a letter in the recipient's mailbox, alerting them if they're online
+fn deliver_letter(world: &mut World, to: &str, letter: Letter) -> anyhow::Result<()> {
+    let from = letter.from.clone();
+    let store = world.resource::<MailStore>();
+    let mut mailbox = store.load(to)?;
+    mailbox.letters.push(letter);
+    store.save(to, &mailbox)?;
+
+    let online = world
+        .query::<(Entity, &Name)>()
+        .iter(world)
+        .find(|(_, n)| n.0.eq_ignore_ascii_case(to))
+        .map(|(ent, _)| ent);
+    if let Some(recipient) = online {
+        reply(
+            world,
+            recipient,
+            format!("You have new mail from {}.", from),
+        );
+    }
+    Ok(())
+}
+
+/// Whether a character by this name exists to receive mail
+pub fn mail_recipient_exists(world: &World, name: &str) -> bool {
+    world.resource::<PlayerStore>().exists(name)
+}
+
+/// Tells a player entering the game about unread letters
+pub fn notify_mail(world: &mut World, player: Entity) {
+    let Some(name) = world.get::<Name>(player).map(|n| n.0.clone()) else {
+        return;
+    };
+    let unread = match world.resource::<MailStore>().load(&name) {
+        Ok(mailbox) => mailbox.unread(),
+        Err(e) => {
+            error!("Failed to read mailbox for {}: {:?}", name, e);
+            return;
+        }
+    };
+    if unread > 0 {
+        reply(
+            world,
+            player,
+            format!(
+                "You have {} unread letter{}. Type 'mail' to read.",
+                unread,
+                if unread == 1 { "" } else { "s" }
+            ),
+        );
+    }
+}
@@ -0,0 +1,268 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+use crate::core::components::{Room, Zone};
+use crate::core::pathing::concealed;
+use crate::core::world::{RoomRegistry, ZoneRegistry};
+
+use bevy_ecs::prelude::*;
+
+/// Rooms the minimap reaches out in each direction.
+pub const MAP_RADIUS: i32 = 3;
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Renders every registered room as a Graphviz digraph, one cluster per zone
+pub fn to_dot(world: &World) -> String {
+    let registry = world.resource::<RoomRegistry>();
+    let zone_names = &world.resource::<ZoneRegistry>().id_to_name;
+
+    let mut zones: BTreeMap<String, Vec<(&str, &Room)>> = BTreeMap::new();
+    let mut edges: Vec<(&str, &str, &str)> = Vec::new();
+    for (id, ent) in &registry.id_to_entity {
+        let Some(room) = world.get::<Room>(*ent) else {
+            continue;
+        };
+        let zone = world
+            .get::<Zone>(*ent)
+            .map_or(String::new(), |z| z.0.clone());
+        zones.entry(zone).or_default().push((id.as_str(), room));
+        for (dir, target) in &room.exits {
+            if let Some(target_id) = registry.id_of(*target) {
+                edges.push((id.as_str(), target_id, dir.as_str()));
+            }
+        }
+    }
+    edges.sort();
+
+    let mut out = String::from("digraph world {\n    node [shape=box];\n");
+    for (zone, mut rooms) in zones {
+        rooms.sort_by_key(|(id, _)| *id);
+        let label = zone_names.get(&zone).unwrap_or(&zone);
+        let _ = writeln!(
+            out,
+            "    subgraph {} {{",
+            quote(&format!("cluster_{}", zone))
+        );
+        let _ = writeln!(out, "        label={};", quote(label));
+        for (id, room) in rooms {
+            let _ = writeln!(out, "        {} [label={}];", quote(id), quote(&room.name));
+        }
+        out.push_str("    }\n");
+    }
+    for (from, to, dir) in edges {
+        let _ = writeln!(
+            out,
+            "    {} -> {} [label={}];",
+            quote(from),
+            quote(to),
+            quote(dir)
+        );
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Grid offset of a compass exit; other exits can't be laid out on the map
+fn offset(dir: &str) -> Option<(i32, i32)> {
+    match dir {
+        "north" => Some((0, -1)),
+        "south" => Some((0, 1)),
+        "east" => Some((1, 0)),
+        "west" => Some((-1, 0)),
+        _ => None,
+    }
+}
+
+/// Draws the rooms within `radius` steps of `origin`, placed by exit direction
+pub fn minimap(world: &World, origin: Entity, radius: i32) -> String {
+    let mut placed: HashMap<(i32, i32), Entity> = HashMap::new();
+    let mut seen: HashSet<Entity> = HashSet::new();
+    let mut queue = VecDeque::from([(origin, 0, 0)]);
+    placed.insert((0, 0), origin);
+    seen.insert(origin);
+
+    while let Some((room, x, y)) = queue.pop_front() {
+        let Some(exits) = world.get::<Room>(room).map(|r| &r.exits) else {
+            continue;
+        };
+        // Sorted so rooms competing for one cell always resolve the same way.
+        let mut exits: Vec<(&String, &Entity)> = exits.iter().collect();
+        exits.sort();
+        for (dir, target) in exits {
+            if concealed(world, room, dir) {
+                continue;
+            }
+            let Some((dx, dy)) = offset(dir) else {
+                continue;
+            };
+            let (nx, ny) = (x + dx, y + dy);
+            if nx.abs() > radius || ny.abs() > radius || seen.contains(target) {
+                continue;
+            }
+            if let std::collections::hash_map::Entry::Vacant(slot) = placed.entry((nx, ny)) {
+                slot.insert(*target);
+                seen.insert(*target);
+                queue.push_back((*target, nx, ny));
+            }
+        }
+    }
+
+    // Each room is drawn as "[ ]" with connectors between neighbours.
+    let size = (2 * radius + 1) as usize;
+    let mut grid = vec![vec![' '; size * 4 - 1]; size * 2 - 1];
+    for (&(x, y), &room) in &placed {
+        let row = ((y + radius) * 2) as usize;
+        let col = ((x + radius) * 4) as usize;
+        grid[row][col] = '[';
+        grid[row][col + 1] = if room == origin { '@' } else { ' ' };
+        grid[row][col + 2] = ']';
+
+        let Some(exits) = world.get::<Room>(room).map(|r| &r.exits) else {
+            continue;
+        };
+        let linked = |dir: &str, at: (i32, i32)| {
+            exits.get(dir).is_some_and(|t| placed.get(&at) == Some(t))
+                && !concealed(world, room, dir)
+        };
+        if linked("east", (x + 1, y)) {
+            grid[row][col + 3] = '-';
+        }
+        if linked("south", (x, y + 1)) {
+            grid[row + 1][col + 1] = '|';
+        }
+        if linked("west", (x - 1, y)) && col > 0 {
+            grid[row][col - 1] = '-';
+        }
+        if linked("north", (x, y - 1)) && row > 0 {
+            grid[row - 1][col + 1] = '|';
+        }
+    }
+
+    let lines: Vec<String> = grid
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>().trim_end().to_string())
+        .collect();
+    let first = lines.iter().position(|l| !l.is_empty()).unwrap_or(0);
+    let last = lines.iter().rposition(|l| !l.is_empty()).unwrap_or(0);
+    let lines = &lines[first..=last];
+    let indent = lines
+        .iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::components::ConcealedExits;
+    use crate::core::world::create_room;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.insert_resource(RoomRegistry::default());
+        world.insert_resource(ZoneRegistry::default());
+        world
+    }
+
+    fn room(world: &mut World, id: &str, name: &str) -> Entity {
+        let ent = create_room(world, name, "");
+        let zone = id.split_once(':').map_or("", |(z, _)| z).to_string();
+        world.entity_mut(ent).insert(Zone(zone));
+        world
+            .resource_mut::<RoomRegistry>()
+            .insert(id.to_string(), ent);
+        ent
+    }
+
+    fn link(world: &mut World, from: Entity, dir: &str, to: Entity) {
+        world
+            .get_mut::<Room>(from)
+            .unwrap()
+            .exits
+            .insert(dir.to_string(), to);
+    }
+
+    /// An origin with a room to the east and one to the north, linked both ways
+    fn cross(world: &mut World) -> Entity {
+        let origin = room(world, "z:origin", "Origin");
+        let east = room(world, "z:east", "East");
+        let north = room(world, "z:north", "North");
+        link(world, origin, "east", east);
+        link(world, east, "west", origin);
+        link(world, origin, "north", north);
+        link(world, north, "south", origin);
+        origin
+    }
+
+    #[test]
+    fn minimap_places_rooms_by_direction() {
+        let mut world = world();
+        let origin = cross(&mut world);
+        assert_eq!(minimap(&world, origin, 1), "[ ]\n |\n[@]-[ ]");
+    }
+
+    #[test]
+    fn minimap_skips_concealed_exits() {
+        let mut world = world();
+        let origin = cross(&mut world);
+        world
+            .entity_mut(origin)
+            .insert(ConcealedExits(["east".to_string()].into()));
+        assert_eq!(minimap(&world, origin, 1), "[ ]\n |\n[@]");
+    }
+
+    #[test]
+    fn minimap_stays_within_radius() {
+        let mut world = world();
+        let origin = room(&mut world, "z:a", "A");
+        let mut prev = origin;
+        for id in ["z:b", "z:c", "z:d"] {
+            let next = room(&mut world, id, id);
+            link(&mut world, prev, "east", next);
+            prev = next;
+        }
+        assert_eq!(minimap(&world, origin, 2), "[@]-[ ]-[ ]");
+    }
+
+    #[test]
+    fn dot_clusters_zones_and_labels_exits() {
+        let mut world = world();
+        world
+            .resource_mut::<ZoneRegistry>()
+            .id_to_name
+            .insert("z".to_string(), "Zone Z".to_string());
+        let a = room(&mut world, "z:a", "Alpha");
+        let b = room(&mut world, "z:b", "Beta \"two\"");
+        link(&mut world, a, "east", b);
+        assert_eq!(
+            to_dot(&world),
+            "digraph world {\n    node [shape=box];\n    subgraph \"cluster_z\" {\n        label=\"Zone Z\";\n        \"z:a\" [label=\"Alpha\"];\n        \"z:b\" [label=\"Beta \\\"two\\\"\"];\n    }\n    \"z:a\" -> \"z:b\" [label=\"east\"];\n}\n"
+        );
+    }
+}
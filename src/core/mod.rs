@@ -14,12 +14,38 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+pub mod channels;
+pub mod clock;
+pub mod combat;
 pub mod commands;
 pub mod components;
+pub mod death;
+pub mod dialogue;
+pub mod economy;
+pub mod effects;
+pub mod environment;
 pub mod events;
+pub mod groups;
+pub mod input;
+pub mod mail;
+pub mod mapping;
+pub mod moderation;
+pub mod npc;
+pub mod olc;
 pub mod password;
+pub mod pathing;
 pub mod plugins;
+pub mod progression;
+pub mod quests;
+pub mod race;
+pub mod scripting;
+pub mod socials;
+pub mod stealth;
+pub mod storage;
 pub mod systems;
+pub mod text;
+pub mod util;
+pub mod visibility;
 pub mod world;
 
 pub use commands::CommandMap;
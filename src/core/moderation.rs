@@ -0,0 +1,91 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::VecDeque;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+use crate::core::util::now_secs;
+
+use bevy_ecs::prelude::*;
+use tracing::error;
+
+/// Lines of the reporter's scrollback attached to each report.
+pub const REPORT_EVIDENCE_LINES: usize = 20;
+const RECENT_REPORTS: usize = 50;
+
+/// Player reports, appended to disk together with the evidence each one carries.
+#[derive(Resource)]
+pub struct ReportLog {
+    path: PathBuf,
+    /// One summary line per recent report, without its evidence.
+    pub recent: VecDeque<String>,
+}
+
+impl ReportLog {
+    /// Opens the log at `path`, seeding `recent` with the summaries it already holds
+    pub fn new(path: &str) -> Self {
+        let existing = fs::read_to_string(path).unwrap_or_default();
+        let summaries: Vec<&str> = existing
+            .lines()
+            .filter(|l| !l.starts_with("  | "))
+            .collect();
+        let recent = summaries[summaries.len().saturating_sub(RECENT_REPORTS)..]
+            .iter()
+            .map(|l| l.to_string())
+            .collect();
+        Self {
+            path: PathBuf::from(path),
+            recent,
+        }
+    }
+
+    pub fn record(&mut self, reporter: &str, accused: &str, reason: &str, evidence: &[String]) {
+        let summary = format!(
+            "{} {} reported {}: {}",
+            now_secs(),
+            reporter,
+            accused,
+            reason
+        );
+
+        if let Some(dir) = self.path.parent()
+            && let Err(e) = fs::create_dir_all(dir)
+        {
+            error!("Failed to create {:?}: {}", dir, e);
+        }
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut f| {
+                writeln!(f, "{}", summary)?;
+                for line in evidence {
+                    writeln!(f, "  | {}", line)?;
+                }
+                Ok(())
+            });
+        if let Err(e) = written {
+            error!("Failed to write report log: {}", e);
+        }
+
+        if self.recent.len() == RECENT_REPORTS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(summary);
+    }
+}
@@ -0,0 +1,486 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::clock::Schedule;
+use crate::core::combat::Fighting;
+use crate::core::commands::movement::StdExits;
+use crate::core::components::{
+    Attributes, Experience, Health, Location, Name, Player, Race, Room, RoomFlag, Zone,
+};
+use crate::core::dialogue::Dialogue;
+use crate::core::economy::{Shop, ShopDef};
+use crate::core::effects::Effects;
+use crate::core::events::BroadcastRoomEvent;
+use crate::core::pathing::{next_step, passable};
+use crate::core::scripting::{ScriptEngine, TriggerDefs};
+use crate::core::text::{capitalize, display_name};
+use crate::core::visibility::can_see;
+use crate::core::world::{
+    GameTick, ItemDef, TICKS_PER_SECOND, has_flag, is_default, qualify, spawn_item,
+};
+
+use bevy_ecs::prelude::*;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use serde::{Deserialize, Serialize};
+
+// === NPC Definitions ===
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct BehaviorDef {
+    /// Roam between exits that stay inside the home zone.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub wander: bool,
+    /// Attack hostile targets on sight.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub aggressive: bool,
+    /// Never wander, and return to the home room when displaced.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub sentinel: bool,
+    /// Flee when health drops below this percentage; 0 never flees.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub flee_below: u32,
+    /// Races attacked on sight; empty means any player.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hostile_to: Vec<Race>,
+    /// Ticks between decisions.
+    #[serde(
+        default = "default_think_interval",
+        skip_serializing_if = "is_default_think_interval"
+    )]
+    pub think_interval: u64,
+    /// Chance of wandering on each decision.
+    #[serde(
+        default = "default_wander_chance",
+        skip_serializing_if = "is_default_wander_chance"
+    )]
+    pub wander_chance: f64,
+}
+
+fn default_think_interval() -> u64 {
+    2 * TICKS_PER_SECOND
+}
+
+fn is_default_think_interval(interval: &u64) -> bool {
+    *interval == default_think_interval()
+}
+
+fn default_wander_chance() -> f64 {
+    0.25
+}
+
+fn is_default_wander_chance(chance: &f64) -> bool {
+    *chance == default_wander_chance()
+}
+
+impl Default for BehaviorDef {
+    fn default() -> Self {
+        Self {
+            wander: false,
+            aggressive: false,
+            sentinel: false,
+            flee_below: 0,
+            hostile_to: Vec::new(),
+            think_interval: default_think_interval(),
+            wander_chance: default_wander_chance(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReactionDef {
+    pub keyword: String,
+    pub response: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct NpcDef {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Local id of the room the NPC spawns in.
+    pub room: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub race: Option<Race>,
+    #[serde(default = "default_level", skip_serializing_if = "is_default_level")]
+    pub level: u32,
+    /// Overrides the race or default maximum health.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health: Option<u32>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub behavior: BehaviorDef,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<ReactionDef>,
+    /// Local id of the dialogue tree used by `talk`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dialogue: Option<String>,
+    #[serde(default, skip_serializing_if = "TriggerDefs::is_empty")]
+    pub triggers: TriggerDefs,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shop: Option<ShopDef>,
+    /// Items carried, and dropped into the corpse on death.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub inventory: Vec<ItemDef>,
+    /// Where the NPC keeps its post through the day.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedule: Vec<ScheduleDef>,
+}
+
+/// From `hour` (0-23) on, the NPC's post moves to `room`, a room id in the same zone.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ScheduleDef {
+    pub hour: u64,
+    pub room: String,
+}
+
+fn default_level() -> u32 {
+    1
+}
+
+fn is_default_level(level: &u32) -> bool {
+    *level == default_level()
+}
+
+// === NPC Components ===
+
+#[derive(Component)]
+pub struct Npc {
+    /// Registry-style id of the definition this NPC was spawned from.
+    pub template: String,
+    pub description: String,
+}
+
+#[derive(Component)]
+pub struct Behavior {
+    pub def: BehaviorDef,
+    pub next_think: u64,
+}
+
+#[derive(Component)]
+pub struct Home(pub Entity);
+
+#[derive(Component)]
+pub struct Reactions(pub Vec<ReactionDef>);
+
+/// Every NPC definition with its zone and home room, keyed by template id, for zone resets.
+#[derive(Resource, Default)]
+pub struct NpcTemplates(pub HashMap<String, (String, NpcDef, Entity)>);
+
+pub fn spawn_npc(world: &mut World, zone_id: &str, def: &NpcDef, room: Entity) -> Entity {
+    let (attrs, mut health) = match def.race {
+        Some(race) => (race.base_attributes(), race.base_health()),
+        None => (
+            Attributes {
+                strength: 10,
+                agility: 10,
+                intellect: 10,
+            },
+            Health {
+                current: 50,
+                max: 50,
+            },
+        ),
+    };
+    health.max = match def.health {
+        Some(max) => max,
+        None => health.max + def.level.saturating_sub(1) * 10,
+    };
+    health.current = health.max;
+
+    let ent = world
+        .spawn((
+            Npc {
+                template: format!("{}:{}", zone_id, def.id),
+                description: def.description.clone(),
+            },
+            Name(def.name.clone()),
+            Location(room),
+            Home(room),
+            attrs,
+            health,
+            Experience {
+                level: def.level,
+                xp: 0,
+            },
+            Effects::default(),
+            Behavior {
+                def: def.behavior.clone(),
+                next_think: 0,
+            },
+            Reactions(def.reactions.clone()),
+        ))
+        .id();
+
+    if let Some(race) = def.race {
+        world.entity_mut(ent).insert(race);
+    }
+    if let Some(shop) = &def.shop {
+        world.entity_mut(ent).insert(Shop::new(shop.clone()));
+    }
+    if !def.schedule.is_empty() {
+        let schedule = def
+            .schedule
+            .iter()
+            .map(|s| (s.hour, qualify(zone_id, &s.room)))
+            .collect();
+        world.entity_mut(ent).insert(Schedule(schedule));
+    }
+    for item in &def.inventory {
+        spawn_item(world, item, ent);
+    }
+    if let Some(dialogue) = &def.dialogue {
+        world
+            .entity_mut(ent)
+            .insert(Dialogue(format!("{}:{}", zone_id, dialogue)));
+    }
+    if !def.triggers.is_empty() {
+        let triggers = world
+            .resource_mut::<ScriptEngine>()
+            .compile_triggers(&format!("{}:{}", zone_id, def.id), &def.triggers);
+        world.entity_mut(ent).insert(triggers);
+    }
+    ent
+}
+
+/// Respawns every NPC from `zone` that is no longer in the world
+pub fn respawn_missing(world: &mut World, zone: &str) {
+    let alive: HashSet<String> = world
+        .query::<&Npc>()
+        .iter(world)
+        .map(|n| n.template.clone())
+        .collect();
+    let missing: Vec<(NpcDef, Entity)> = world
+        .resource::<NpcTemplates>()
+        .0
+        .iter()
+        .filter(|(id, (z, _, _))| z == zone && !alive.contains(*id))
+        .map(|(_, (_, def, room))| (def.clone(), *room))
+        .collect();
+
+    for (def, room) in missing {
+        let npc = spawn_npc(world, zone, &def, room);
+        let name = capitalize(&display_name(world, npc));
+        world.write_message(BroadcastRoomEvent {
+            from: npc,
+            room,
+            text: format!("{} arrives.", name),
+            unseen: Some("Someone arrives.".to_string()),
+        });
+    }
+}
+
+/// Moves an NPC through an exit with the usual leave/arrive messages
+pub fn move_npc(world: &mut World, npc: Entity, dir: &str, dest: Entity, verb: &str) {
+    let Some(from) = world.get::<Location>(npc).map(|l| l.0) else {
+        return;
+    };
+    let name = capitalize(&display_name(world, npc));
+    if let Some(mut loc) = world.get_mut::<Location>(npc) {
+        loc.0 = dest;
+    }
+
+    let from_dir = StdExits::parse(dir).map(|d| format!(" from {}", d.opposite().as_str_noun()));
+    let from_dir = from_dir.unwrap_or_default();
+    world.write_message(BroadcastRoomEvent {
+        from: npc,
+        room: from,
+        text: format!("{} {} {}.", name, verb, dir),
+        unseen: Some(format!("Someone {} {}.", verb, dir)),
+    });
+    world.write_message(BroadcastRoomEvent {
+        from: npc,
+        room: dest,
+        text: format!("{} arrives{}.", name, from_dir),
+        unseen: Some(format!("Someone arrives{}.", from_dir)),
+    });
+}
+
+/// Exits an NPC could take right now, skipping closed doors
+fn exits_of(world: &World, room: Entity) -> Vec<(String, Entity)> {
+    world
+        .get::<Room>(room)
+        .map(|r| {
+            r.exits
+                .iter()
+                .filter(|(d, _)| passable(world, room, d))
+                .map(|(d, e)| (d.clone(), *e))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Walks an NPC one step along the shortest path to `target`, returning false if there's no way there
+pub fn step_toward(world: &mut World, npc: Entity, target: Entity, verb: &str) -> bool {
+    let Some(room) = world.get::<Location>(npc).map(|l| l.0) else {
+        return false;
+    };
+    match next_step(world, room, target) {
+        Some((dir, dest)) => {
+            move_npc(world, npc, &dir, dest, verb);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Whether `npc`, acting on `behavior`, would attack `target` on sight
+fn is_hostile(world: &World, npc: Entity, behavior: &BehaviorDef, target: Entity) -> bool {
+    if !can_see(world, npc, target) {
+        return false;
+    }
+    if behavior.hostile_to.is_empty() {
+        return world.get::<Player>(target).is_some();
+    }
+    world
+        .get::<Race>(target)
+        .is_some_and(|r| behavior.hostile_to.contains(r))
+}
+
+/// Lets each NPC whose think timer has elapsed decide what to do next
+pub fn npc_think(world: &mut World) {
+    let tick = world.resource::<GameTick>().0;
+    let due: Vec<Entity> = world
+        .query::<(Entity, &Behavior)>()
+        .iter(world)
+        .filter(|(_, b)| b.next_think <= tick)
+        .map(|(ent, _)| ent)
+        .collect();
+
+    for npc in due {
+        let Some(def) = world.get::<Behavior>(npc).map(|b| b.def.clone()) else {
+            continue;
+        };
+        if let Some(mut behavior) = world.get_mut::<Behavior>(npc) {
+            behavior.next_think = tick + def.think_interval.max(1);
+        }
+        think(world, npc, &def);
+    }
+}
+
+fn think(world: &mut World, npc: Entity, def: &BehaviorDef) {
+    let Some(room) = world.get::<Location>(npc).map(|l| l.0) else {
+        return;
+    };
+    let mut rng = rand::rng();
+    let fighting = world.get::<Fighting>(npc).is_some();
+
+    if fighting {
+        let wounded = world
+            .get::<Health>(npc)
+            .is_some_and(|h| h.current * 100 < h.max * def.flee_below);
+        let options: Vec<(String, Entity)> = exits_of(world, room)
+            .into_iter()
+            .filter(|(_, dest)| !has_flag(world, *dest, RoomFlag::DeathTrap))
+            .collect();
+        if wounded && let Some((dir, dest)) = options.choose(&mut rng).cloned() {
+            world.entity_mut(npc).remove::<Fighting>();
+            move_npc(world, npc, &dir, dest, "flees");
+        }
+        return;
+    }
+
+    if def.aggressive && !has_flag(world, room, RoomFlag::Safe) {
+        let target = world
+            .query::<(Entity, &Location, &Health)>()
+            .iter(world)
+            .filter(|(ent, loc, health)| *ent != npc && loc.0 == room && health.current > 0)
+            .map(|(ent, _, _)| ent)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .find(|ent| is_hostile(world, npc, def, *ent));
+        if let Some(target) = target {
+            let name = capitalize(&display_name(world, npc));
+            let target_name = world
+                .get::<Name>(target)
+                .map_or("someone".to_string(), |n| n.0.clone());
+            world.entity_mut(npc).insert(Fighting(target));
+            world.write_message(BroadcastRoomEvent {
+                from: npc,
+                room,
+                text: format!("{} attacks {}!", name, target_name),
+                unseen: Some(format!("Someone attacks {}!", target_name)),
+            });
+            return;
+        }
+    }
+
+    // Scheduled NPCs keep to their current post just like sentinels.
+    if def.sentinel || world.get::<Schedule>(npc).is_some() {
+        if let Some(home) = world.get::<Home>(npc).map(|h| h.0)
+            && home != room
+            && !step_toward(world, npc, home, "heads")
+        {
+            // Cut off from its post, so it finds its own way back.
+            let name = capitalize(&display_name(world, npc));
+            world.write_message(BroadcastRoomEvent {
+                from: npc,
+                room,
+                text: format!("{} hurries off.", name),
+                unseen: Some("Someone hurries off.".to_string()),
+            });
+            if let Some(mut loc) = world.get_mut::<Location>(npc) {
+                loc.0 = home;
+            }
+            world.write_message(BroadcastRoomEvent {
+                from: npc,
+                room: home,
+                text: format!("{} returns to its post.", name),
+                unseen: Some("Someone returns to its post.".to_string()),
+            });
+        }
+        return;
+    }
+
+    if def.wander && rng.random_bool(def.wander_chance.clamp(0.0, 1.0)) {
+        let zone = world.get::<Zone>(room).map(|z| z.0.clone());
+        let options: Vec<(String, Entity)> = exits_of(world, room)
+            .into_iter()
+            .filter(|(_, dest)| world.get::<Zone>(*dest).map(|z| z.0.clone()) == zone)
+            .filter(|(_, dest)| !has_flag(world, *dest, RoomFlag::DeathTrap))
+            .collect();
+        if let Some((dir, dest)) = options.choose(&mut rng).cloned() {
+            move_npc(world, npc, &dir, dest, "leaves");
+        }
+    }
+}
+
+/// Lets NPCs in `room` answer speech containing one of their keywords
+pub fn react_to_speech(world: &mut World, room: Entity, speaker: Entity, message: &str) {
+    let message = message.to_lowercase();
+    let replies: Vec<(Entity, String, String)> = world
+        .query::<(Entity, &Location, &Name, &Reactions)>()
+        .iter(world)
+        .filter(|(ent, loc, _, _)| *ent != speaker && loc.0 == room)
+        .filter_map(|(ent, _, name, reactions)| {
+            reactions
+                .0
+                .iter()
+                .find(|r| message.contains(&r.keyword.to_lowercase()))
+                .map(|r| (ent, capitalize(&name.0), r.response.clone()))
+        })
+        .collect();
+
+    for (npc, name, response) in replies {
+        world.write_message(BroadcastRoomEvent {
+            from: npc,
+            room,
+            text: format!("{} says: {}", name, response),
+            unseen: Some(format!("Someone says: {}", response)),
+        });
+    }
+}
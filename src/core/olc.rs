@@ -0,0 +1,202 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::reply;
+use crate::core::components::{ConcealedExits, Doors, Location, Room, Zone};
+use crate::core::mail::Board;
+use crate::core::npc::{Home, NpcTemplates};
+use crate::core::world::{
+    RoomRegistry, ZoneDef, ZoneRegistry, ZoneSource, create_room, default_reset_minutes, qualify,
+    reset_ticks,
+};
+
+use bevy_ecs::prelude::*;
+
+/// Description given to rooms fresh out of `dig` and `zcreate`.
+pub const UNFINISHED_DESCRIPTION: &str = "An unfinished room. Nothing has been described here yet.";
+
+/// Records that a zone has unsaved edits
+pub fn mark_dirty(world: &mut World, zone: &str) {
+    world
+        .resource_mut::<ZoneRegistry>()
+        .dirty
+        .insert(zone.to_string());
+}
+
+/// Removes the exit `dir` from `room` along with any door or concealment on it
+pub fn remove_exit(world: &mut World, room: Entity, dir: &str) -> bool {
+    let removed = world
+        .get_mut::<Room>(room)
+        .and_then(|mut r| r.exits.remove(dir))
+        .is_some();
+    if let Some(mut doors) = world.get_mut::<Doors>(room) {
+        doors.0.remove(dir);
+    }
+    if let Some(mut concealed) = world.get_mut::<ConcealedExits>(room) {
+        concealed.0.remove(dir);
+    }
+    removed
+}
+
+/// Zone a room belongs to
+pub fn zone_of(world: &World, room: Entity) -> Option<String> {
+    world.get::<Zone>(room).map(|z| z.0.clone())
+}
+
+/// Whether a zone or room id is safe to write into a zone file
+pub fn valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Turns a room name into an id not yet used in the zone
+pub fn unique_room_id(world: &World, zone: &str, name: &str) -> String {
+    let mut base: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    base = base.trim_matches('_').to_string();
+    if base.is_empty() {
+        base = "room".to_string();
+    }
+
+    let registry = world.resource::<RoomRegistry>();
+    let mut id = base.clone();
+    let mut n = 2;
+    while registry.get(&qualify(zone, &id)).is_some() {
+        id = format!("{}_{}", base, n);
+        n += 1;
+    }
+    id
+}
+
+/// Spawns an empty room in a zone and registers it under `zone:id`
+pub fn add_room(world: &mut World, zone: &str, id: &str, name: &str) -> Entity {
+    let room = create_room(world, name, UNFINISHED_DESCRIPTION);
+    world.entity_mut(room).insert(Zone(zone.to_string()));
+    world
+        .resource_mut::<RoomRegistry>()
+        .insert(qualify(zone, id), room);
+    mark_dirty(world, zone);
+    room
+}
+
+/// Removes a room and its board, moving everything else inside it, and every NPC that calls it
+/// home, to `fallback`
+pub fn delete_room(world: &mut World, room: Entity, fallback: Entity) {
+    let boards: Vec<Entity> = world
+        .query_filtered::<(Entity, &Location), With<Board>>()
+        .iter(world)
+        .filter(|(_, loc)| loc.0 == room)
+        .map(|(ent, _)| ent)
+        .collect();
+    for board in boards {
+        world.despawn(board);
+    }
+
+    let occupants: Vec<Entity> = world
+        .query::<(Entity, &Location)>()
+        .iter(world)
+        .filter(|(_, loc)| loc.0 == room)
+        .map(|(ent, _)| ent)
+        .collect();
+    for ent in occupants {
+        world.entity_mut(ent).insert(Location(fallback));
+    }
+
+    let homed: Vec<Entity> = world
+        .query::<(Entity, &Home)>()
+        .iter(world)
+        .filter(|(_, home)| home.0 == room)
+        .map(|(ent, _)| ent)
+        .collect();
+    for ent in homed {
+        world.entity_mut(ent).insert(Home(fallback));
+    }
+    for (_, _, home) in world.resource_mut::<NpcTemplates>().0.values_mut() {
+        if *home == room {
+            *home = fallback;
+        }
+    }
+
+    let severed: Vec<(Entity, String)> = world
+        .query::<(Entity, &Room)>()
+        .iter(world)
+        .flat_map(|(other, r)| {
+            r.exits
+                .iter()
+                .filter(|(_, target)| **target == room)
+                .map(move |(dir, _)| (other, dir.clone()))
+        })
+        .collect();
+    for (other, dir) in severed {
+        remove_exit(world, other, &dir);
+        if let Some(zone) = zone_of(world, other) {
+            mark_dirty(world, &zone);
+        }
+    }
+
+    let mut registry = world.resource_mut::<RoomRegistry>();
+    if let Some(id) = registry.entity_to_id.remove(&room) {
+        registry.id_to_entity.remove(&id);
+    }
+    if let Some(zone) = zone_of(world, room) {
+        mark_dirty(world, &zone);
+    }
+    world.despawn(room);
+}
+
+/// Registers a new, empty zone with a single `start` room, returning that room
+pub fn create_zone(world: &mut World, id: &str, name: &str) -> Entity {
+    let mut zones = world.resource_mut::<ZoneRegistry>();
+    let path = zones.dir.join(format!("{}.toml", id));
+    let def = ZoneDef {
+        id: id.to_string(),
+        name: name.to_string(),
+        respawn: None,
+        reset_minutes: default_reset_minutes(),
+        rooms: Vec::new(),
+        npcs: Vec::new(),
+        dialogues: Vec::new(),
+        quests: Vec::new(),
+    };
+    zones.id_to_name.insert(id.to_string(), name.to_string());
+    zones
+        .resets
+        .insert(id.to_string(), reset_ticks(def.reset_minutes));
+    zones
+        .sources
+        .insert(id.to_string(), ZoneSource { path, def });
+    add_room(world, id, "start", name)
+}
+
+/// Replaces a room's description with the lines written in the editor
+pub fn set_description(world: &mut World, player: Entity, room: Entity, lines: Vec<String>) {
+    let text = match world.get_mut::<Room>(room) {
+        Some(mut r) => {
+            r.description = lines.join("\n");
+            if let Some(zone) = zone_of(world, room) {
+                mark_dirty(world, &zone);
+            }
+            "Description saved.".to_string()
+        }
+        None => "That room no longer exists.".to_string(),
+    };
+    reply(world, player, text);
+}
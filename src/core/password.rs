@@ -34,13 +34,12 @@ pub fn hash_password(password: &str) -> String {
     hasher.update(password.as_bytes());
     let hash = hasher.finalize();
 
-    let salt_b64 = STANDARD.encode(&salt);
+    let salt_b64 = STANDARD.encode(salt);
     let hash_b64 = STANDARD.encode(hash.as_bytes());
 
     format!("BLAKE3${}${}", salt_b64, hash_b64)
 }
 
-#[allow(dead_code)]
 pub fn verify_password(password: &str, stored: &str) -> bool {
     let parts: Vec<&str> = stored.split('$').collect();
     if parts.len() != 3 || parts[0] != "BLAKE3" {
@@ -0,0 +1,270 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::core::combat::Fighting;
+use crate::core::commands::movement::{StdExits, cmd_move};
+use crate::core::commands::reply;
+use crate::core::components::{ConcealedExits, Doors, Frozen, Landmark, Location, Room, RoomFlag};
+use crate::core::world::{GameTick, TICKS_PER_SECOND, has_flag};
+
+use bevy_ecs::prelude::*;
+
+/// Ticks between steps when a player travels somewhere.
+pub const TRAVEL_STEP_TICKS: u64 = TICKS_PER_SECOND / 2;
+
+/// Whether the exit `dir` out of `room` is still waiting to be found
+pub fn concealed(world: &World, room: Entity, dir: &str) -> bool {
+    world
+        .get::<ConcealedExits>(room)
+        .is_some_and(|c| c.0.contains(dir))
+}
+
+/// Whether the exit `dir` out of `room` can be walked through right now
+pub fn passable(world: &World, room: Entity, dir: &str) -> bool {
+    !concealed(world, room, dir) && !world.get::<Doors>(room).is_some_and(|d| d.is_closed(dir))
+}
+
+/// Exits out of `room` that aren't blocked by a closed door, in a stable order
+fn open_exits(world: &World, room: Entity) -> Vec<(String, Entity)> {
+    let Some(r) = world.get::<Room>(room) else {
+        return Vec::new();
+    };
+    let mut exits: Vec<(String, Entity)> = r
+        .exits
+        .iter()
+        .filter(|(dir, _)| passable(world, room, dir))
+        .map(|(dir, target)| (dir.clone(), *target))
+        .collect();
+    exits.sort_by(|a, b| a.0.cmp(&b.0));
+    exits
+}
+
+/// Shortest route from `from` to `to` as a list of exits, crossing zones freely but never through a death trap
+pub fn find_path(world: &World, from: Entity, to: Entity) -> Option<Vec<String>> {
+    if from == to {
+        return Some(Vec::new());
+    }
+    let mut came_from: HashMap<Entity, (Entity, String)> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(room) = queue.pop_front() {
+        for (dir, next) in open_exits(world, room) {
+            if next == from || came_from.contains_key(&next) {
+                continue;
+            }
+            if next != to && has_flag(world, next, RoomFlag::DeathTrap) {
+                continue;
+            }
+            came_from.insert(next, (room, dir));
+            if next == to {
+                let mut route = Vec::new();
+                let mut at = to;
+                while let Some((prev, dir)) = came_from.get(&at) {
+                    route.push(dir.clone());
+                    at = *prev;
+                }
+                route.reverse();
+                return Some(route);
+            }
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+/// The first exit to take from `from` towards `to`, and the room it leads to
+pub fn next_step(world: &World, from: Entity, to: Entity) -> Option<(String, Entity)> {
+    let dir = find_path(world, from, to)?.into_iter().next()?;
+    let target = world.get::<Room>(from)?.exits.get(&dir).copied()?;
+    Some((dir, target))
+}
+
+/// Collapses a route into speedwalk directions, e.g. "2 north, east"
+pub fn describe_route(route: &[String]) -> String {
+    let mut legs: Vec<(usize, &str)> = Vec::new();
+    for dir in route {
+        match legs.last_mut() {
+            Some((count, last)) if *last == dir.as_str() => *count += 1,
+            _ => legs.push((1, dir)),
+        }
+    }
+    legs.iter()
+        .map(|(count, dir)| match count {
+            1 => dir.to_string(),
+            n => format!("{} {}", n, dir),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Every landmark in the world as (name, room), sorted by name
+pub fn landmarks(world: &mut World) -> Vec<(String, Entity)> {
+    let mut found: Vec<(String, Entity)> = world
+        .query::<(Entity, &Landmark)>()
+        .iter(world)
+        .map(|(ent, l)| (l.0.clone(), ent))
+        .collect();
+    found.sort();
+    found
+}
+
+/// Finds a landmark whose name starts with `name`, case-insensitively
+pub fn find_landmark(world: &mut World, name: &str) -> Option<(String, Entity)> {
+    let name = name.to_lowercase();
+    landmarks(world)
+        .into_iter()
+        .find(|(landmark, _)| landmark.to_lowercase().starts_with(&name))
+}
+
+/// A player walking to a destination one step at a time.
+#[derive(Component, Debug)]
+pub struct Travelling {
+    pub destination: Entity,
+    pub next_step: u64,
+}
+
+fn stop_travelling(world: &mut World, player: Entity, text: &str) {
+    world.entity_mut(player).remove::<Travelling>();
+    reply(world, player, text.to_string());
+}
+
+/// Moves each travelling player one step closer, re-planning every step so opened or closed doors count
+pub fn walk_travellers(world: &mut World) {
+    let tick = world.resource::<GameTick>().0;
+    let due: Vec<(Entity, Entity)> = world
+        .query::<(Entity, &Travelling)>()
+        .iter(world)
+        .filter(|(_, t)| t.next_step <= tick)
+        .map(|(ent, t)| (ent, t.destination))
+        .collect();
+
+    for (player, destination) in due {
+        let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+            world.entity_mut(player).remove::<Travelling>();
+            continue;
+        };
+        if world.get::<Fighting>(player).is_some() {
+            stop_travelling(world, player, "You stop travelling to fight.");
+            continue;
+        }
+        if world.get::<Frozen>(player).is_some() {
+            stop_travelling(world, player, "You are frozen solid and stop travelling.");
+            continue;
+        }
+        let Some(dir) =
+            next_step(world, room, destination).and_then(|(dir, _)| StdExits::parse(&dir))
+        else {
+            stop_travelling(world, player, "You can no longer find a way there.");
+            continue;
+        };
+        cmd_move(dir, player, world, dir.as_str(), &[]);
+
+        let now = world.get::<Location>(player).map(|l| l.0);
+        if now == Some(room) {
+            stop_travelling(world, player, "You stop travelling.");
+        } else if now == Some(destination) {
+            let name = world
+                .get::<Room>(destination)
+                .map_or(String::new(), |r| r.name.clone());
+            stop_travelling(world, player, &format!("You have arrived at {}.", name));
+        } else if let Some(mut travelling) = world.get_mut::<Travelling>(player) {
+            travelling.next_step = tick + TRAVEL_STEP_TICKS;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::components::RoomFlags;
+    use crate::core::world::create_room;
+
+    fn link(world: &mut World, from: Entity, dir: &str, to: Entity) {
+        world
+            .get_mut::<Room>(from)
+            .unwrap()
+            .exits
+            .insert(dir.to_string(), to);
+    }
+
+    /// `a` reaches `c` two ways: straight east through `b`, or the long way round via `d` and `e`
+    fn rooms() -> (World, [Entity; 5]) {
+        let mut world = World::new();
+        let [a, b, c, d, e] = ["a", "b", "c", "d", "e"].map(|n| create_room(&mut world, n, ""));
+        link(&mut world, a, "east", b);
+        link(&mut world, b, "east", c);
+        link(&mut world, a, "north", d);
+        link(&mut world, d, "east", e);
+        link(&mut world, e, "east", c);
+        (world, [a, b, c, d, e])
+    }
+
+    fn route(dirs: &[&str]) -> Option<Vec<String>> {
+        Some(dirs.iter().map(|d| d.to_string()).collect())
+    }
+
+    #[test]
+    fn takes_the_shortest_route() {
+        let (world, [a, _, c, _, _]) = rooms();
+        assert_eq!(find_path(&world, a, c), route(&["east", "east"]));
+        assert_eq!(find_path(&world, a, a), route(&[]));
+        assert_eq!(find_path(&world, c, a), None);
+    }
+
+    #[test]
+    fn closed_doors_force_a_detour() {
+        let (mut world, [a, _, c, _, _]) = rooms();
+        world
+            .entity_mut(a)
+            .insert(Doors([("east".to_string(), true)].into()));
+        assert_eq!(find_path(&world, a, c), route(&["north", "east", "east"]));
+
+        world
+            .get_mut::<Doors>(a)
+            .unwrap()
+            .0
+            .insert("east".to_string(), false);
+        assert_eq!(find_path(&world, a, c), route(&["east", "east"]));
+    }
+
+    #[test]
+    fn concealed_exits_are_not_used() {
+        let (mut world, [a, _, c, d, _]) = rooms();
+        world
+            .entity_mut(a)
+            .insert(ConcealedExits(["north".to_string()].into()));
+        assert_eq!(find_path(&world, a, c), route(&["east", "east"]));
+        assert_eq!(find_path(&world, a, d), None);
+    }
+
+    #[test]
+    fn death_traps_are_avoided_unless_they_are_the_goal() {
+        let (mut world, [a, b, c, _, _]) = rooms();
+        world
+            .entity_mut(b)
+            .insert(RoomFlags([RoomFlag::DeathTrap].into()));
+        assert_eq!(find_path(&world, a, c), route(&["north", "east", "east"]));
+        assert_eq!(find_path(&world, a, b), route(&["east"]));
+    }
+
+    #[test]
+    fn routes_collapse_into_speedwalk() {
+        let dirs = route(&["north", "north", "east", "south", "south", "south"]).unwrap();
+        assert_eq!(describe_route(&dirs), "2 north, east, 3 south");
+        assert_eq!(describe_route(&[]), "");
+    }
+}
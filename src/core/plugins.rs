@@ -15,13 +15,26 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::core::CommandMap;
+use crate::core::channels::{ChannelRegistry, flush_channels};
+use crate::core::clock::{GameClock, ZoneWeather, advance_clock};
+use crate::core::combat::combat_rounds;
+use crate::core::death::{decay_corpses, handle_deaths};
+use crate::core::effects::{apply_effects, tick_effects};
+use crate::core::environment::{disengage_in_safe_rooms, environmental_damage};
 use crate::core::events::BroadcastRoomEvent;
 use crate::core::events::{
-    BroadcastEvent, BroadcastZoneEvent, CommandEvent, DisconnectEvent, OutputEvent,
+    ApplyEffectEvent, BroadcastEvent, BroadcastZoneEvent, ChannelEvent, CommandEvent, DamageEvent,
+    DeathEvent, DisconnectEvent, EmoteEvent, ExperienceEvent, OutputEvent, QuestEvent, SaveEvent,
 };
+use crate::core::npc::npc_think;
+use crate::core::pathing::walk_travellers;
+use crate::core::quests::track_quests;
+use crate::core::scripting::{ScriptEngine, run_tick_scripts};
 use crate::core::systems::{
-    flush_broadcasts, flush_broadcasts_room, flush_broadcasts_zone, flush_output,
+    advance_tick, apply_damage, award_experience, flush_broadcasts, flush_broadcasts_room,
+    flush_broadcasts_zone, flush_emotes, flush_output, regenerate_stamina, save_characters,
 };
+use crate::core::world::{GameTick, RespawnPoint, reset_zones};
 
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
@@ -30,19 +43,49 @@ pub struct CorePlugin;
 
 impl Plugin for CorePlugin {
     fn build(&self, app: &mut App) {
-        app.add_message::<BroadcastEvent>()
+        app.add_message::<ApplyEffectEvent>()
+            .add_message::<BroadcastEvent>()
             .add_message::<BroadcastRoomEvent>()
             .add_message::<BroadcastZoneEvent>()
+            .add_message::<ChannelEvent>()
             .add_message::<CommandEvent>()
+            .add_message::<DamageEvent>()
+            .add_message::<DeathEvent>()
             .add_message::<DisconnectEvent>()
+            .add_message::<EmoteEvent>()
+            .add_message::<ExperienceEvent>()
             .add_message::<OutputEvent>()
+            .add_message::<QuestEvent>()
+            .add_message::<SaveEvent>()
+            .insert_resource(ChannelRegistry::default())
             .insert_resource(CommandMap::new())
+            .insert_resource(GameClock::default())
+            .insert_resource(GameTick::default())
+            .insert_resource(RespawnPoint::default())
+            .insert_resource(ScriptEngine::new())
+            .insert_resource(ZoneWeather::default())
             .add_systems(
                 Update,
                 (
+                    (advance_tick, advance_clock).chain(),
+                    reset_zones,
+                    tick_effects,
+                    (npc_think, walk_travellers).chain(),
+                    (disengage_in_safe_rooms, combat_rounds, environmental_damage).chain(),
+                    apply_damage,
+                    handle_deaths,
+                    decay_corpses,
+                    regenerate_stamina,
+                    apply_effects,
+                    run_tick_scripts,
+                    track_quests,
+                    award_experience,
+                    save_characters,
                     flush_broadcasts,
                     flush_broadcasts_room,
+                    flush_emotes,
                     flush_broadcasts_zone,
+                    flush_channels,
                     flush_output,
                 )
                     .chain(),
@@ -0,0 +1,25 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+pub const MAX_LEVEL: u32 = 50;
+
+/// Experience awarded the first time a character enters a room.
+pub const EXPLORE_XP: u64 = 10;
+
+/// Total experience required to advance past `level`
+pub fn next_level_xp(level: u32) -> u64 {
+    100 * u64::from(level) * u64::from(level)
+}
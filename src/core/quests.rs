@@ -0,0 +1,357 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::commands::items::items_held_by;
+use crate::core::commands::reply;
+use crate::core::components::Flags;
+use crate::core::events::{ExperienceEvent, QuestEvent, SaveEvent};
+use crate::core::world::{ItemDef, is_default, qualify, spawn_item};
+
+use bevy_ecs::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// === Quest Definitions ===
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectiveKind {
+    /// Kill NPCs spawned from `target`.
+    Kill,
+    /// Carry items matching the keyword `target`.
+    Fetch,
+    /// Enter the room `target`.
+    Visit,
+    /// Start a conversation with NPCs spawned from `target`.
+    Talk,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ObjectiveDef {
+    pub kind: ObjectiveKind,
+    pub target: String,
+    #[serde(default = "default_count", skip_serializing_if = "is_default_count")]
+    pub count: u32,
+    /// Journal line, e.g. "Slay the drone in the corridor".
+    pub text: String,
+}
+
+fn default_count() -> u32 {
+    1
+}
+
+fn is_default_count(count: &u32) -> bool {
+    *count == default_count()
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+pub struct RewardDef {
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub xp: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<ItemDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct QuestDef {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub objectives: Vec<ObjectiveDef>,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub reward: RewardDef,
+}
+
+impl QuestDef {
+    /// Rewrites zone-local NPC and room targets as registry ids
+    pub fn qualified(mut self, zone: &str) -> Self {
+        for obj in &mut self.objectives {
+            if matches!(
+                obj.kind,
+                ObjectiveKind::Kill | ObjectiveKind::Visit | ObjectiveKind::Talk
+            ) {
+                obj.target = qualify(zone, &obj.target);
+            }
+        }
+        self
+    }
+}
+
+/// Every loaded quest, keyed by "zone:id".
+#[derive(Resource, Default)]
+pub struct QuestRegistry(pub HashMap<String, QuestDef>);
+
+/// Gameplay that can advance an objective.
+#[derive(Debug, Clone)]
+pub enum QuestTrigger {
+    /// Template id of the NPC that was killed.
+    Kill(String),
+    /// Something was picked up or a quest taken on, so fetch objectives recount what is carried.
+    Pickup,
+    /// Registry id of the room entered.
+    Visit(String),
+    /// Template id of the NPC spoken to.
+    Talk(String),
+}
+
+impl QuestTrigger {
+    fn satisfies(&self, obj: &ObjectiveDef) -> bool {
+        match (self, obj.kind) {
+            (QuestTrigger::Kill(id), ObjectiveKind::Kill)
+            | (QuestTrigger::Visit(id), ObjectiveKind::Visit)
+            | (QuestTrigger::Talk(id), ObjectiveKind::Talk) => *id == obj.target,
+            _ => false,
+        }
+    }
+}
+
+// === Quest Components ===
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuestProgress {
+    pub quest: String,
+    /// Count reached for each objective, in definition order.
+    pub progress: Vec<u32>,
+}
+
+#[derive(Component, Debug, Default)]
+pub struct QuestLog {
+    pub active: Vec<QuestProgress>,
+    pub completed: HashSet<String>,
+}
+
+impl QuestLog {
+    pub fn is_active(&self, quest: &str) -> bool {
+        self.active.iter().any(|q| q.quest == quest)
+    }
+}
+
+/// Adds `quest` to the player's journal; false if it is unknown, active or already done
+pub fn accept_quest(world: &mut World, player: Entity, quest: &str) -> bool {
+    let Some(def) = world.resource::<QuestRegistry>().0.get(quest).cloned() else {
+        return false;
+    };
+    let Some(mut log) = world.get_mut::<QuestLog>(player) else {
+        return false;
+    };
+    if log.is_active(quest) || log.completed.contains(quest) {
+        return false;
+    }
+    log.active.push(QuestProgress {
+        quest: quest.to_string(),
+        progress: vec![0; def.objectives.len()],
+    });
+
+    reply(
+        world,
+        player,
+        format!("New quest: {}\n{}", def.name, def.description),
+    );
+    // Items already in hand count towards the new quest straight away.
+    world.write_message(QuestEvent {
+        player,
+        trigger: QuestTrigger::Pickup,
+    });
+    world.write_message(SaveEvent { player });
+    true
+}
+
+/// Advances objectives from this tick's gameplay and pays out finished quests
+pub fn track_quests(world: &mut World) {
+    let events: Vec<(Entity, QuestTrigger)> = world
+        .resource_mut::<Messages<QuestEvent>>()
+        .drain()
+        .map(|e| (e.player, e.trigger))
+        .collect();
+
+    for (player, trigger) in events {
+        let Some(mut log) = world.get_mut::<QuestLog>(player).map(|l| l.active.clone()) else {
+            continue;
+        };
+
+        let held = items_held_by(world, player);
+        let mut advanced = Vec::new();
+        let mut finished = Vec::new();
+        for entry in &mut log {
+            let Some(def) = world.resource::<QuestRegistry>().0.get(&entry.quest) else {
+                continue;
+            };
+            for (obj, count) in def.objectives.iter().zip(entry.progress.iter_mut()) {
+                if obj.kind == ObjectiveKind::Fetch {
+                    // Counted from what is carried so one item can't be picked up twice.
+                    let carried = held.iter().filter(|(_, i)| i.matches(&obj.target)).count();
+                    let carried = (carried as u32).min(obj.count);
+                    if carried > *count {
+                        advanced.push(format!("{} ({}/{})", obj.text, carried, obj.count));
+                    }
+                    *count = carried;
+                } else if *count < obj.count && trigger.satisfies(obj) {
+                    *count += 1;
+                    advanced.push(format!("{} ({}/{})", obj.text, count, obj.count));
+                }
+            }
+            if def
+                .objectives
+                .iter()
+                .zip(&entry.progress)
+                .all(|(obj, count)| *count >= obj.count)
+            {
+                finished.push(entry.quest.clone());
+            }
+        }
+        if advanced.is_empty() {
+            continue;
+        }
+
+        log.retain(|q| !finished.contains(&q.quest));
+        if let Some(mut quests) = world.get_mut::<QuestLog>(player) {
+            quests.active = log;
+            quests.completed.extend(finished.iter().cloned());
+        }
+        for line in advanced {
+            reply(world, player, format!("Quest progress: {}", line));
+        }
+        for quest in finished {
+            complete(world, player, &quest);
+        }
+        world.write_message(SaveEvent { player });
+    }
+}
+
+fn complete(world: &mut World, player: Entity, quest: &str) {
+    let Some(def) = world.resource::<QuestRegistry>().0.get(quest).cloned() else {
+        return;
+    };
+    reply(world, player, format!("Quest complete: {}!", def.name));
+
+    let reward = def.reward;
+    if reward.xp > 0 {
+        world.write_message(ExperienceEvent {
+            player,
+            amount: reward.xp,
+        });
+    }
+    for item in reward.items {
+        reply(world, player, format!("You receive {}.", item.name));
+        spawn_item(world, &item, player);
+    }
+    if let Some(mut flags) = world.get_mut::<Flags>(player) {
+        flags.0.extend(reward.flags);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::OutputEvent;
+
+    fn item(name: &str) -> ItemDef {
+        ItemDef {
+            name: format!("a {}", name),
+            keywords: vec![name.to_string()],
+            description: String::new(),
+            value: 0,
+            light: false,
+        }
+    }
+
+    /// A world holding one fetch quest for two crystals, and a player with an empty log
+    fn setup() -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<Messages<QuestEvent>>();
+        world.init_resource::<Messages<OutputEvent>>();
+        world.init_resource::<Messages<SaveEvent>>();
+        world.init_resource::<Messages<ExperienceEvent>>();
+        let mut registry = QuestRegistry::default();
+        registry.0.insert(
+            "test:crystals".to_string(),
+            QuestDef {
+                id: "crystals".to_string(),
+                name: "Crystals".to_string(),
+                description: String::new(),
+                objectives: vec![ObjectiveDef {
+                    kind: ObjectiveKind::Fetch,
+                    target: "crystal".to_string(),
+                    count: 2,
+                    text: "Bring two crystals".to_string(),
+                }],
+                reward: RewardDef {
+                    flags: vec!["crystals_done".to_string()],
+                    ..Default::default()
+                },
+            },
+        );
+        world.insert_resource(registry);
+        let player = world.spawn((QuestLog::default(), Flags::default())).id();
+        (world, player)
+    }
+
+    fn pickup(world: &mut World, player: Entity) {
+        world.write_message(QuestEvent {
+            player,
+            trigger: QuestTrigger::Pickup,
+        });
+        track_quests(world);
+    }
+
+    #[test]
+    fn fetch_counts_carried_items() {
+        let (mut world, player) = setup();
+        assert!(accept_quest(&mut world, player, "test:crystals"));
+        spawn_item(&mut world, &item("crystal"), player);
+        pickup(&mut world, player);
+        let log = world.get::<QuestLog>(player).unwrap();
+        assert_eq!(log.active[0].progress, vec![1]);
+
+        spawn_item(&mut world, &item("crystal"), player);
+        spawn_item(&mut world, &item("crystal"), player);
+        pickup(&mut world, player);
+        let log = world.get::<QuestLog>(player).unwrap();
+        assert!(log.active.is_empty());
+        assert!(log.completed.contains("test:crystals"));
+        assert!(
+            world
+                .get::<Flags>(player)
+                .unwrap()
+                .0
+                .contains("crystals_done")
+        );
+    }
+
+    #[test]
+    fn fetch_ignores_other_items() {
+        let (mut world, player) = setup();
+        accept_quest(&mut world, player, "test:crystals");
+        spawn_item(&mut world, &item("rock"), player);
+        pickup(&mut world, player);
+        let log = world.get::<QuestLog>(player).unwrap();
+        assert_eq!(log.active[0].progress, vec![0]);
+    }
+
+    #[test]
+    fn accepting_counts_items_already_carried() {
+        let (mut world, player) = setup();
+        spawn_item(&mut world, &item("crystal"), player);
+        spawn_item(&mut world, &item("crystal"), player);
+        accept_quest(&mut world, player, "test:crystals");
+        track_quests(&mut world);
+        let log = world.get::<QuestLog>(player).unwrap();
+        assert!(log.completed.contains("test:crystals"));
+    }
+}
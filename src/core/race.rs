@@ -0,0 +1,137 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::components::{Attributes, Health, Race, Stamina};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RaceTrait {
+    /// Attackers in the same room are splashed when the bearer is wounded.
+    AcidBlood,
+    /// Movement is never announced by name.
+    Stealth,
+    /// May `hiss` to frighten those nearby.
+    Terrify,
+    /// May bend light around itself with `cloak`.
+    Cloaking,
+    /// May fire the shoulder-mounted `plasma` caster.
+    PlasmaCaster,
+    /// Unaffected by `Frightened`.
+    FearImmune,
+    /// Never spends stamina.
+    FatigueImmune,
+    /// Doesn't need to breathe, so vacuum does no harm.
+    VacuumImmune,
+    /// Shrugs off toxic and irradiated rooms.
+    HazardImmune,
+    /// Sees living bodies by their heat, through darkness and cloaks alike.
+    ThermalVision,
+    /// Sees in the dark as well as in the light.
+    DarkVision,
+    /// May use improvised tools such as `flare` and `medkit`.
+    ToolUse,
+}
+
+impl Race {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Race::Alien => "Xenomorph",
+            Race::Human => "Human",
+            Race::Predator => "Predator",
+            Race::SyntheticHuman => "Synthetic",
+        }
+    }
+
+    pub fn traits(&self) -> &'static [RaceTrait] {
+        match self {
+            Race::Alien => &[
+                RaceTrait::AcidBlood,
+                RaceTrait::Stealth,
+                RaceTrait::Terrify,
+                RaceTrait::VacuumImmune,
+                RaceTrait::DarkVision,
+            ],
+            Race::Human => &[RaceTrait::ToolUse],
+            Race::Predator => &[
+                RaceTrait::Cloaking,
+                RaceTrait::PlasmaCaster,
+                RaceTrait::HazardImmune,
+                RaceTrait::ThermalVision,
+            ],
+            Race::SyntheticHuman => &[
+                RaceTrait::FearImmune,
+                RaceTrait::FatigueImmune,
+                RaceTrait::VacuumImmune,
+                RaceTrait::HazardImmune,
+            ],
+        }
+    }
+
+    pub fn has_trait(&self, t: RaceTrait) -> bool {
+        self.traits().contains(&t)
+    }
+
+    pub fn base_attributes(&self) -> Attributes {
+        let (strength, agility, intellect) = match self {
+            Race::Alien => (14, 16, 6),
+            Race::Human => (10, 10, 12),
+            Race::Predator => (16, 12, 10),
+            Race::SyntheticHuman => (14, 10, 16),
+        };
+        Attributes {
+            strength,
+            agility,
+            intellect,
+        }
+    }
+
+    pub fn base_health(&self) -> Health {
+        let max = match self {
+            Race::Alien => 120,
+            Race::Human => 100,
+            Race::Predator => 140,
+            Race::SyntheticHuman => 110,
+        };
+        Health { current: max, max }
+    }
+
+    pub fn base_stamina(&self) -> Stamina {
+        let max = match self {
+            Race::Alien => 60,
+            Race::Human => 50,
+            Race::Predator => 60,
+            Race::SyntheticHuman => 50,
+        };
+        Stamina { current: max, max }
+    }
+
+    /// Attribute and maximum health increases granted on each level-up
+    pub fn level_gains(&self) -> (Attributes, u32) {
+        let (strength, agility, intellect, health) = match self {
+            Race::Alien => (1, 2, 0, 12),
+            Race::Human => (1, 1, 2, 10),
+            Race::Predator => (2, 1, 1, 14),
+            Race::SyntheticHuman => (1, 1, 2, 11),
+        };
+        (
+            Attributes {
+                strength,
+                agility,
+                intellect,
+            },
+            health,
+        )
+    }
+}
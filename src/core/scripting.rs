@@ -0,0 +1,390 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::sync::{Arc, Mutex};
+
+use crate::core::commands::reply;
+use crate::core::components::{Location, Name, Race, Room, RoomFlag};
+use crate::core::events::BroadcastRoomEvent;
+use crate::core::world::{GameTick, RoomRegistry, TICKS_PER_SECOND, has_flag};
+
+use bevy_ecs::prelude::*;
+use rhai::{AST, Array, Dynamic, Engine, Map, Scope};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+const MAX_OPERATIONS: u64 = 50_000;
+
+/// Trigger sources as written in zone files.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TriggerDefs {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_enter: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_say: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_tick: Option<String>,
+    /// Ticks between `on_tick` runs.
+    #[serde(
+        default = "default_tick_interval",
+        skip_serializing_if = "is_default_tick_interval"
+    )]
+    pub tick_interval: u64,
+}
+
+fn default_tick_interval() -> u64 {
+    TICKS_PER_SECOND
+}
+
+fn is_default_tick_interval(interval: &u64) -> bool {
+    *interval == default_tick_interval()
+}
+
+impl Default for TriggerDefs {
+    fn default() -> Self {
+        Self {
+            on_enter: None,
+            on_say: None,
+            on_command: None,
+            on_tick: None,
+            tick_interval: default_tick_interval(),
+        }
+    }
+}
+
+impl TriggerDefs {
+    pub fn is_empty(&self) -> bool {
+        self.on_enter.is_none()
+            && self.on_say.is_none()
+            && self.on_command.is_none()
+            && self.on_tick.is_none()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TriggerKind {
+    Enter,
+    Say,
+    Command,
+    Tick,
+}
+
+/// Compiled triggers attached to a room, item or NPC; values index `ScriptEngine::scripts`.
+#[derive(Component, Debug, Default)]
+pub struct Triggers {
+    pub on_enter: Option<usize>,
+    pub on_say: Option<usize>,
+    pub on_command: Option<usize>,
+    pub on_tick: Option<usize>,
+    pub tick_interval: u64,
+}
+
+impl Triggers {
+    fn get(&self, kind: TriggerKind) -> Option<usize> {
+        match kind {
+            TriggerKind::Enter => self.on_enter,
+            TriggerKind::Say => self.on_say,
+            TriggerKind::Command => self.on_command,
+            TriggerKind::Tick => self.on_tick,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ScriptAction {
+    Send { target: Entity, text: String },
+    BroadcastRoom { room: String, text: String },
+    Move { target: Entity, room: String },
+}
+
+/// Sandboxed Rhai engine plus every compiled trigger.
+///
+/// Scripts never touch the ECS directly: they read a snapshot of the actor and
+/// room as Rhai maps, and calls such as `send` queue a `ScriptAction` that is
+/// applied once the script returns.
+#[derive(Resource)]
+pub struct ScriptEngine {
+    engine: Engine,
+    scripts: Vec<AST>,
+    actions: Arc<Mutex<Vec<ScriptAction>>>,
+}
+
+impl Default for ScriptEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn entity_from_id(id: i64) -> Option<Entity> {
+    Entity::try_from_bits(id as u64)
+}
+
+impl ScriptEngine {
+    pub fn new() -> Self {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(MAX_OPERATIONS)
+            .set_max_call_levels(16)
+            .set_max_expr_depths(64, 32)
+            .set_max_string_size(4096)
+            .set_max_array_size(256)
+            .set_max_map_size(256)
+            .disable_symbol("eval")
+            .on_print(|text| info!("[script] {}", text))
+            .on_debug(|text, _, pos| info!("[script] {:?} {}", pos, text));
+
+        let actions: Arc<Mutex<Vec<ScriptAction>>> = Arc::default();
+
+        let queue = actions.clone();
+        engine.register_fn("send", move |target: i64, text: &str| {
+            if let Some(target) = entity_from_id(target) {
+                queue.lock().unwrap().push(ScriptAction::Send {
+                    target,
+                    text: text.to_string(),
+                });
+            }
+        });
+
+        let queue = actions.clone();
+        engine.register_fn("broadcast_room", move |room: &str, text: &str| {
+            queue.lock().unwrap().push(ScriptAction::BroadcastRoom {
+                room: room.to_string(),
+                text: text.to_string(),
+            });
+        });
+
+        let queue = actions.clone();
+        engine.register_fn("move_entity", move |target: i64, room: &str| {
+            if let Some(target) = entity_from_id(target) {
+                queue.lock().unwrap().push(ScriptAction::Move {
+                    target,
+                    room: room.to_string(),
+                });
+            }
+        });
+
+        Self {
+            engine,
+            scripts: Vec::new(),
+            actions,
+        }
+    }
+
+    pub fn compile(&mut self, source: &str) -> anyhow::Result<usize> {
+        let ast = self
+            .engine
+            .compile(source)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        self.scripts.push(ast);
+        Ok(self.scripts.len() - 1)
+    }
+
+    /// Compiles every trigger in `defs`, naming `owner` in any error
+    pub fn compile_triggers(&mut self, owner: &str, defs: &TriggerDefs) -> Triggers {
+        let mut compile = |kind: &str, source: &Option<String>| {
+            source.as_deref().and_then(|src| match self.compile(src) {
+                Ok(idx) => Some(idx),
+                Err(e) => {
+                    error!("Failed to compile {} trigger for {}: {}", kind, owner, e);
+                    None
+                }
+            })
+        };
+        Triggers {
+            on_enter: compile("on_enter", &defs.on_enter),
+            on_say: compile("on_say", &defs.on_say),
+            on_command: compile("on_command", &defs.on_command),
+            on_tick: compile("on_tick", &defs.on_tick),
+            tick_interval: defs.tick_interval.max(1),
+        }
+    }
+}
+
+fn entity_map(world: &World, ent: Entity) -> Map {
+    let mut map = Map::new();
+    map.insert("id".into(), Dynamic::from(ent.to_bits() as i64));
+    let name = world
+        .get::<Name>(ent)
+        .map(|n| n.0.clone())
+        .unwrap_or_default();
+    map.insert("name".into(), name.into());
+    let race = world
+        .get::<Race>(ent)
+        .map(|r| r.label().to_string())
+        .unwrap_or_default();
+    map.insert("race".into(), race.into());
+    map
+}
+
+fn room_map(world: &mut World, room: Entity) -> Map {
+    let mut map = Map::new();
+    let id = |world: &World, ent: Entity| {
+        world
+            .resource::<RoomRegistry>()
+            .id_of(ent)
+            .unwrap_or_default()
+            .to_string()
+    };
+    map.insert("id".into(), id(world, room).into());
+
+    if let Some(r) = world.get::<Room>(room) {
+        map.insert("name".into(), r.name.clone().into());
+        map.insert("description".into(), r.description.clone().into());
+        let exits: Map = r
+            .exits
+            .iter()
+            .map(|(dir, target)| (dir.as_str().into(), id(world, *target).into()))
+            .collect();
+        map.insert("exits".into(), exits.into());
+    }
+
+    let occupants: Vec<Entity> = world
+        .query::<(Entity, &Location, &Name)>()
+        .iter(world)
+        .filter(|(_, loc, _)| loc.0 == room)
+        .map(|(ent, _, _)| ent)
+        .collect();
+    let occupants: Array = occupants
+        .into_iter()
+        .map(|ent| entity_map(world, ent).into())
+        .collect();
+    map.insert("occupants".into(), occupants.into());
+    map
+}
+
+/// Runs one compiled script and applies whatever it queued; returns the script's boolean result
+fn run(
+    world: &mut World,
+    script: usize,
+    owner: Entity,
+    room: Entity,
+    actor: Option<Entity>,
+    vars: &[(&str, Dynamic)],
+) -> bool {
+    let mut scope = Scope::new();
+    scope.push_constant("room", room_map(world, room));
+    scope.push_constant("me", entity_map(world, owner));
+    match actor {
+        Some(actor) => scope.push_constant("actor", entity_map(world, actor)),
+        None => scope.push_constant("actor", Dynamic::UNIT),
+    };
+    for (name, value) in vars {
+        scope.push_constant(*name, value.clone());
+    }
+
+    let (result, actions) = world.resource_scope(|_, engine: Mut<ScriptEngine>| {
+        let result = engine
+            .engine
+            .eval_ast_with_scope::<Dynamic>(&mut scope, &engine.scripts[script]);
+        let actions = std::mem::take(&mut *engine.actions.lock().unwrap());
+        (result, actions)
+    });
+
+    for action in actions {
+        apply(world, owner, action);
+    }
+
+    match result {
+        Ok(value) => value.as_bool().unwrap_or(false),
+        Err(e) => {
+            error!("Script error: {}", e);
+            false
+        }
+    }
+}
+
+fn apply(world: &mut World, owner: Entity, action: ScriptAction) {
+    match action {
+        ScriptAction::Send { target, text } => {
+            if world.get_entity(target).is_ok() {
+                reply(world, target, text);
+            }
+        }
+        ScriptAction::BroadcastRoom { room, text } => {
+            if let Some(room) = world.resource::<RoomRegistry>().get(&room) {
+                world.write_message(BroadcastRoomEvent {
+                    from: owner,
+                    room,
+                    text,
+                    unseen: None,
+                });
+            }
+        }
+        ScriptAction::Move { target, room } => {
+            let dest = world.resource::<RoomRegistry>().get(&room);
+            let pinned = |room: Entity| has_flag(world, room, RoomFlag::NoTeleport);
+            let here = world.get::<Location>(target).map(|l| l.0);
+            if let Some(dest) = dest
+                && !pinned(dest)
+                && !here.is_some_and(pinned)
+                && let Some(mut loc) = world.get_mut::<Location>(target)
+            {
+                loc.0 = dest;
+            }
+        }
+    }
+}
+
+/// Fires `kind` triggers on `room` and everything in it; true if any script claimed the event
+pub fn fire(
+    world: &mut World,
+    room: Entity,
+    actor: Option<Entity>,
+    kind: TriggerKind,
+    vars: &[(&str, Dynamic)],
+) -> bool {
+    let mut owners: Vec<(Entity, usize)> = Vec::new();
+    if let Some(script) = world.get::<Triggers>(room).and_then(|t| t.get(kind)) {
+        owners.push((room, script));
+    }
+    owners.extend(
+        world
+            .query::<(Entity, &Location, &Triggers)>()
+            .iter(world)
+            .filter(|(ent, loc, _)| loc.0 == room && Some(*ent) != actor)
+            .filter_map(|(ent, _, t)| t.get(kind).map(|s| (ent, s))),
+    );
+
+    let mut handled = false;
+    for (owner, script) in owners {
+        handled |= run(world, script, owner, room, actor, vars);
+    }
+    handled
+}
+
+/// Runs `on_tick` triggers whose interval has elapsed
+pub fn run_tick_scripts(world: &mut World) {
+    let tick = world.resource::<GameTick>().0;
+    let due: Vec<(Entity, usize)> = world
+        .query::<(Entity, &Triggers)>()
+        .iter(world)
+        .filter(|(_, t)| tick.is_multiple_of(t.tick_interval))
+        .filter_map(|(ent, t)| t.get(TriggerKind::Tick).map(|s| (ent, s)))
+        .collect();
+
+    for (owner, script) in due {
+        let room = match world.get::<Room>(owner) {
+            Some(_) => owner,
+            None => match world.get::<Location>(owner) {
+                Some(loc) => loc.0,
+                None => continue,
+            },
+        };
+        run(world, script, owner, room, None, &[]);
+    }
+}
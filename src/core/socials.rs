@@ -0,0 +1,164 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+use std::fs;
+
+use crate::core::CommandMap;
+use crate::core::commands::{find_in_room, is_muted, reply};
+use crate::core::components::{Ignoring, Location, Name};
+use crate::core::events::EmoteEvent;
+use crate::core::stealth::reveal;
+use crate::core::text::{capitalize, display_name};
+
+use anyhow::Context;
+use bevy_ecs::prelude::*;
+use serde::Deserialize;
+
+/// Lines for a social used without a target. `{actor}` is replaced by the actor's name.
+#[derive(Deserialize, Debug, Clone)]
+pub struct UntargetedDef {
+    pub actor: String,
+    pub room: String,
+}
+
+/// Lines for a social aimed at someone else. `{target}` is replaced by their name.
+#[derive(Deserialize, Debug, Clone)]
+pub struct TargetedDef {
+    pub actor: String,
+    pub target: String,
+    pub room: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SocialDef {
+    pub name: String,
+    pub no_target: UntargetedDef,
+    #[serde(default)]
+    pub target: Option<TargetedDef>,
+    #[serde(default)]
+    pub self_target: Option<UntargetedDef>,
+}
+
+#[derive(Deserialize, Debug)]
+struct SocialsFile {
+    socials: Vec<SocialDef>,
+}
+
+/// Every social, keyed by command name.
+#[derive(Resource, Default)]
+pub struct Socials(pub HashMap<String, SocialDef>);
+
+/// Loads the socials table and registers each social as a command
+pub fn load_socials(world: &mut World, path: &str) -> anyhow::Result<()> {
+    let contents = fs::read_to_string(path).with_context(|| format!("reading {}", path))?;
+    let file: SocialsFile =
+        toml::from_str(&contents).with_context(|| format!("parsing {}", path))?;
+
+    let names: Vec<String> = file.socials.iter().map(|s| s.name.clone()).collect();
+    world.resource_mut::<CommandMap>().register_socials(&names);
+    world.insert_resource(Socials(
+        file.socials
+            .into_iter()
+            .map(|s| (s.name.clone(), s))
+            .collect(),
+    ));
+    Ok(())
+}
+
+fn fill(template: &str, actor: &str, target: &str) -> String {
+    template
+        .replace("{actor}", actor)
+        .replace("{target}", target)
+}
+
+/// Handler shared by every social; the command word picks which one runs
+pub fn cmd_social(player: Entity, world: &mut World, full: &str, args: &[&str]) {
+    let word = full
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let Some(social) = world.resource::<Socials>().0.get(&word).cloned() else {
+        return;
+    };
+    if is_muted(world, player) {
+        return;
+    }
+    let Some(room) = world.get::<Location>(player).map(|l| l.0) else {
+        return;
+    };
+    reveal(world, player);
+    let name = display_name(world, player);
+
+    let Some(arg) = args.first() else {
+        reply(world, player, fill(&social.no_target.actor, &name, ""));
+        world.write_message(EmoteEvent {
+            from: player,
+            target: None,
+            room,
+            text: fill(&social.no_target.room, &name, ""),
+            unseen: Some(capitalize(&fill(&social.no_target.room, "someone", ""))),
+        });
+        return;
+    };
+
+    let is_self = arg.eq_ignore_ascii_case("self")
+        || arg.eq_ignore_ascii_case("me")
+        || name.eq_ignore_ascii_case(arg);
+    if is_self && let Some(lines) = &social.self_target {
+        reply(world, player, fill(&lines.actor, &name, &name));
+        world.write_message(EmoteEvent {
+            from: player,
+            target: None,
+            room,
+            text: fill(&lines.room, &name, &name),
+            unseen: Some(capitalize(&fill(&lines.room, "someone", "someone"))),
+        });
+        return;
+    }
+
+    let Some(lines) = &social.target else {
+        reply(
+            world,
+            player,
+            format!("You can't {} at anyone.", social.name),
+        );
+        return;
+    };
+    let Some(target) = find_in_room(world, room, arg, player) else {
+        reply(world, player, "They aren't here.".to_string());
+        return;
+    };
+    let target_name = world
+        .get::<Name>(target)
+        .map_or("someone".to_string(), |n| n.0.clone());
+
+    reply(world, player, fill(&lines.actor, &name, &target_name));
+    if !world
+        .get::<Ignoring>(target)
+        .is_some_and(|i| i.contains(&name))
+    {
+        reply(world, target, fill(&lines.target, &name, &target_name));
+    }
+    world.write_message(EmoteEvent {
+        from: player,
+        target: Some(target),
+        room,
+        text: fill(&lines.room, &name, &target_name),
+        unseen: Some(capitalize(&fill(&lines.room, "someone", &target_name))),
+    });
+}
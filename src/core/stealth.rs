@@ -0,0 +1,99 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::commands::reply;
+use crate::core::components::{
+    Attributes, Experience, Hidden, Ignoring, Location, Name, Player, Race,
+};
+use crate::core::race::RaceTrait;
+use crate::core::visibility::can_see_in;
+
+use bevy_ecs::prelude::*;
+use rand::Rng;
+
+/// Bonus for races built to go unseen.
+const STEALTH_RACE_BONUS: i32 = 5;
+/// Bonus for eyes that pick out body heat.
+const THERMAL_BONUS: i32 = 5;
+
+fn level_of(world: &World, ent: Entity) -> i32 {
+    world.get::<Experience>(ent).map_or(1, |e| e.level as i32)
+}
+
+fn has_trait(world: &World, ent: Entity, t: RaceTrait) -> bool {
+    world.get::<Race>(ent).is_some_and(|r| r.has_trait(t))
+}
+
+/// How good `ent` is at staying unnoticed, before the dice
+pub fn stealth_score(world: &World, ent: Entity) -> i32 {
+    let agility = world
+        .get::<Attributes>(ent)
+        .map_or(10, |a| a.agility as i32);
+    let bonus = if has_trait(world, ent, RaceTrait::Stealth) {
+        STEALTH_RACE_BONUS
+    } else {
+        0
+    };
+    agility + level_of(world, ent) * 2 + bonus
+}
+
+/// How good `ent` is at noticing things, before the dice
+pub fn perception_score(world: &World, ent: Entity) -> i32 {
+    let intellect = world
+        .get::<Attributes>(ent)
+        .map_or(10, |a| a.intellect as i32);
+    let bonus = if has_trait(world, ent, RaceTrait::ThermalVision) {
+        THERMAL_BONUS
+    } else {
+        0
+    };
+    intellect + level_of(world, ent) * 2 + bonus
+}
+
+/// A d20 roll on top of `score`
+pub fn roll(score: i32) -> i32 {
+    score + rand::rng().random_range(1..=20)
+}
+
+/// Sends `text` to each player in `room` who can see `sneaker` and beats its stealth roll.
+/// Returns how many noticed.
+pub fn notify_observers(world: &mut World, sneaker: Entity, room: Entity, text: &str) -> usize {
+    let name = world
+        .get::<Name>(sneaker)
+        .map_or(String::new(), |n| n.0.clone());
+    let stealth = stealth_score(world, sneaker);
+    let observers: Vec<Entity> = world
+        .query_filtered::<(Entity, &Location, Option<&Ignoring>), With<Player>>()
+        .iter(world)
+        .filter(|(ent, loc, ignoring)| {
+            *ent != sneaker && loc.0 == room && !ignoring.is_some_and(|i| i.contains(&name))
+        })
+        .map(|(ent, _, _)| ent)
+        .filter(|&ent| can_see_in(world, ent, sneaker, room))
+        .filter(|&ent| roll(perception_score(world, ent)) > roll(stealth))
+        .collect();
+    for &player in &observers {
+        reply(world, player, text.to_string());
+    }
+    observers.len()
+}
+
+/// Brings `ent` out of hiding, telling it so
+pub fn reveal(world: &mut World, ent: Entity) {
+    if world.entity_mut(ent).take::<Hidden>().is_some() {
+        reply(world, ent, "You step out of hiding.".to_string());
+    }
+}
@@ -0,0 +1,413 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::core::channels::{ChannelId, Channels};
+use crate::core::components::{
+    Admin, Attributes, Builder, Credits, Experience, Flags, Frozen, Health, HeldBy, Ignoring, Item,
+    LightSource, Location, Muted, Name, PasswordHash, Race, Value, VisitedRooms,
+};
+use crate::core::effects::{ActiveEffect, Effects};
+use crate::core::mail::{BoardPosts, Mailbox};
+use crate::core::quests::{QuestLog, QuestProgress};
+use crate::core::world::{ItemDef, RoomRegistry, spawn_item};
+
+use anyhow::{Context, anyhow};
+use bevy_ecs::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+/// On-disk character files, one TOML document per player.
+#[derive(Resource)]
+pub struct PlayerStore {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CharacterRecord {
+    pub name: String,
+    pub password_hash: String,
+    pub race: Race,
+    pub level: u32,
+    pub xp: u64,
+    pub strength: u32,
+    pub agility: u32,
+    pub intellect: u32,
+    pub health: u32,
+    pub max_health: u32,
+    #[serde(default)]
+    pub room: Option<String>,
+    #[serde(default)]
+    pub visited: Vec<String>,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    #[serde(default)]
+    pub quests: Vec<QuestProgress>,
+    #[serde(default)]
+    pub completed_quests: Vec<String>,
+    #[serde(default)]
+    pub credits: u64,
+    #[serde(default)]
+    pub ignored: Vec<String>,
+    /// Subscribed channels; absent means every channel the character qualifies for.
+    #[serde(default)]
+    pub channels: Option<Vec<ChannelId>>,
+    #[serde(default)]
+    pub channel_mutes: Vec<ChannelId>,
+    #[serde(default)]
+    pub channel_bans: Vec<ChannelId>,
+    /// Only ever granted by editing the character file.
+    #[serde(default)]
+    pub admin: bool,
+    /// Also granted by editing the character file.
+    #[serde(default)]
+    pub builder: bool,
+    /// Unix time, in seconds, at which a moderator's mute lifts.
+    #[serde(default)]
+    pub muted_until: Option<u64>,
+    #[serde(default)]
+    pub frozen: bool,
+    /// Items carried at logout, respawned on login.
+    #[serde(default)]
+    pub inventory: Vec<ItemDef>,
+    #[serde(default)]
+    pub effects: Vec<ActiveEffect>,
+}
+
+impl PlayerStore {
+    pub fn new(dir: &str) -> Self {
+        Self {
+            dir: PathBuf::from(dir),
+        }
+    }
+
+    fn path(&self, name: &str) -> anyhow::Result<PathBuf> {
+        file_for(&self.dir, name)
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        self.path(name).is_ok_and(|p| p.exists())
+    }
+
+    pub fn load(&self, name: &str) -> anyhow::Result<CharacterRecord> {
+        let path = self.path(name)?;
+        let contents = fs::read_to_string(&path).with_context(|| format!("reading {:?}", path))?;
+        toml::from_str(&contents).with_context(|| format!("parsing {:?}", path))
+    }
+
+    pub fn save(&self, record: &CharacterRecord) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.dir).with_context(|| format!("creating {:?}", self.dir))?;
+        let path = self.path(&record.name)?;
+        let contents = toml::to_string(record)?;
+        fs::write(&path, contents).with_context(|| format!("writing {:?}", path))
+    }
+
+    /// Writes a brand-new character file, failing if one already exists
+    pub fn create(&self, record: &CharacterRecord) -> anyhow::Result<()> {
+        fs::create_dir_all(&self.dir).with_context(|| format!("creating {:?}", self.dir))?;
+        let path = self.path(&record.name)?;
+        let contents = toml::to_string(record)?;
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+            .with_context(|| format!("creating {:?}", path))?;
+        file.write_all(contents.as_bytes())
+            .with_context(|| format!("writing {:?}", path))
+    }
+}
+
+/// Per-player file under `dir`, refusing anything that isn't a valid name so it can't escape the directory
+fn file_for(dir: &Path, name: &str) -> anyhow::Result<PathBuf> {
+    validate_name(name).map_err(|e| anyhow!("invalid name {:?}: {}", name, e))?;
+    Ok(dir.join(format!("{}.toml", name.to_lowercase())))
+}
+
+/// Reads a TOML document, treating a missing file as empty
+fn load_or_default<T: DeserializeOwned + Default>(path: &Path) -> anyhow::Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let contents = fs::read_to_string(path).with_context(|| format!("reading {:?}", path))?;
+    toml::from_str(&contents).with_context(|| format!("parsing {:?}", path))
+}
+
+fn write_toml<T: Serialize>(dir: &Path, path: &Path, value: &T) -> anyhow::Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("creating {:?}", dir))?;
+    let contents = toml::to_string(value)?;
+    fs::write(path, contents).with_context(|| format!("writing {:?}", path))
+}
+
+/// On-disk mailboxes, one TOML document per character.
+#[derive(Resource)]
+pub struct MailStore {
+    dir: PathBuf,
+}
+
+impl MailStore {
+    pub fn new(dir: &str) -> Self {
+        Self {
+            dir: PathBuf::from(dir),
+        }
+    }
+
+    fn path(&self, name: &str) -> anyhow::Result<PathBuf> {
+        file_for(&self.dir, name)
+    }
+
+    pub fn load(&self, name: &str) -> anyhow::Result<Mailbox> {
+        load_or_default(&self.path(name)?)
+    }
+
+    pub fn save(&self, name: &str, mailbox: &Mailbox) -> anyhow::Result<()> {
+        write_toml(&self.dir, &self.path(name)?, mailbox)
+    }
+}
+
+/// On-disk bulletin boards, one TOML document per board.
+#[derive(Resource)]
+pub struct BoardStore {
+    dir: PathBuf,
+}
+
+impl BoardStore {
+    pub fn new(dir: &str) -> Self {
+        Self {
+            dir: PathBuf::from(dir),
+        }
+    }
+
+    /// Board ids are qualified room ids, so the zone separator is swapped out for the file name
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.toml", id.replace(':', "-")))
+    }
+
+    pub fn load(&self, id: &str) -> anyhow::Result<BoardPosts> {
+        load_or_default(&self.path(id))
+    }
+
+    pub fn save(&self, id: &str, board: &BoardPosts) -> anyhow::Result<()> {
+        write_toml(&self.dir, &self.path(id), board)
+    }
+}
+
+/// Names double as file names, so only plain letters are allowed
+pub fn validate_name(name: &str) -> Result<(), &'static str> {
+    if name.len() < 3 || name.len() > 16 {
+        return Err("Names must be between 3 and 16 letters long.");
+    }
+    if !name.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err("Names may only contain letters.");
+    }
+    Ok(())
+}
+
+/// Captures the persistent parts of a player entity
+pub fn snapshot(world: &World, player: Entity) -> Option<CharacterRecord> {
+    let name = world.get::<Name>(player)?;
+    let password_hash = world.get::<PasswordHash>(player)?;
+    let race = world.get::<Race>(player)?;
+    let attrs = world.get::<Attributes>(player)?;
+    let health = world.get::<Health>(player)?;
+    let exp = world.get::<Experience>(player)?;
+    let room = world.get::<Location>(player).and_then(|loc| {
+        world
+            .get_resource::<RoomRegistry>()
+            .and_then(|reg| reg.id_of(loc.0))
+            .map(str::to_string)
+    });
+    let mut visited: Vec<String> = world
+        .get::<VisitedRooms>(player)
+        .map(|v| v.0.iter().cloned().collect())
+        .unwrap_or_default();
+    visited.sort();
+    let mut flags: Vec<String> = world
+        .get::<Flags>(player)
+        .map(|f| f.0.iter().cloned().collect())
+        .unwrap_or_default();
+    flags.sort();
+    let (quests, mut completed_quests) = world
+        .get::<QuestLog>(player)
+        .map(|log| {
+            (
+                log.active.clone(),
+                log.completed.iter().cloned().collect::<Vec<_>>(),
+            )
+        })
+        .unwrap_or_default();
+    completed_quests.sort();
+    let mut ignored: Vec<String> = world
+        .get::<Ignoring>(player)
+        .map(|i| i.0.iter().cloned().collect())
+        .unwrap_or_default();
+    ignored.sort();
+    let sorted = |set: &HashSet<ChannelId>| {
+        let mut list: Vec<ChannelId> = set.iter().copied().collect();
+        list.sort_by_key(|c| c.name());
+        list
+    };
+    let (channels, channel_mutes, channel_bans) = match world.get::<Channels>(player) {
+        Some(c) => (Some(sorted(&c.joined)), sorted(&c.muted), sorted(&c.banned)),
+        None => (None, Vec::new(), Vec::new()),
+    };
+
+    let inventory = world
+        .try_query::<(&Item, &HeldBy, Option<&Value>, Has<LightSource>)>()
+        .map(|mut held| {
+            held.iter(world)
+                .filter(|(_, by, _, _)| by.0 == player)
+                .map(|(item, _, value, light)| ItemDef {
+                    name: item.name.clone(),
+                    keywords: item.keywords.clone(),
+                    description: item.description.clone(),
+                    value: value.map_or(0, |v| v.0),
+                    light,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(CharacterRecord {
+        name: name.0.clone(),
+        password_hash: password_hash.0.clone(),
+        race: *race,
+        level: exp.level,
+        xp: exp.xp,
+        strength: attrs.strength,
+        agility: attrs.agility,
+        intellect: attrs.intellect,
+        health: health.current,
+        max_health: health.max,
+        room,
+        visited,
+        flags,
+        quests,
+        completed_quests,
+        credits: world.get::<Credits>(player).map_or(0, |c| c.0),
+        ignored,
+        channels,
+        channel_mutes,
+        channel_bans,
+        admin: world.get::<Admin>(player).is_some(),
+        builder: world.get::<Builder>(player).is_some(),
+        muted_until: world.get::<Muted>(player).map(|m| m.until),
+        frozen: world.get::<Frozen>(player).is_some(),
+        inventory,
+        effects: world
+            .get::<Effects>(player)
+            .map(|e| e.0.clone())
+            .unwrap_or_default(),
+    })
+}
+
+/// Rebuilds a player's character components from a saved record
+pub fn restore(world: &mut World, player: Entity, record: CharacterRecord) {
+    let location = record
+        .room
+        .as_deref()
+        .and_then(|id| world.resource::<RoomRegistry>().get(id));
+    if let Some(room) = location {
+        world.entity_mut(player).insert(Location(room));
+    }
+
+    world.entity_mut(player).insert((
+        Name(record.name),
+        PasswordHash(record.password_hash),
+        record.race,
+        Attributes {
+            strength: record.strength,
+            agility: record.agility,
+            intellect: record.intellect,
+        },
+        Health {
+            current: record.health.min(record.max_health),
+            max: record.max_health,
+        },
+        record.race.base_stamina(),
+        Experience {
+            level: record.level,
+            xp: record.xp,
+        },
+        VisitedRooms(record.visited.into_iter().collect()),
+        Flags(record.flags.into_iter().collect()),
+        QuestLog {
+            active: record.quests,
+            completed: record.completed_quests.into_iter().collect(),
+        },
+        Credits(record.credits),
+        Ignoring(record.ignored.into_iter().collect()),
+        Effects(record.effects),
+    ));
+    let mut channels = match record.channels {
+        Some(joined) => Channels {
+            joined: joined.into_iter().collect(),
+            ..Default::default()
+        },
+        None => Channels::defaults(Some(record.race), record.admin),
+    };
+    channels.muted = record.channel_mutes.into_iter().collect();
+    channels.banned = record.channel_bans.into_iter().collect();
+    world.entity_mut(player).insert(channels);
+    if record.admin {
+        world.entity_mut(player).insert(Admin);
+    }
+    if record.builder {
+        world.entity_mut(player).insert(Builder);
+    }
+    if let Some(until) = record.muted_until {
+        world.entity_mut(player).insert(Muted { until });
+    }
+    if record.frozen {
+        world.entity_mut(player).insert(Frozen);
+    }
+    for def in &record.inventory {
+        spawn_item(world, def, player);
+    }
+}
+
+/// Writes a player's character file, logging rather than propagating failures
+pub fn save_character(world: &World, player: Entity) {
+    let Some(record) = snapshot(world, player) else {
+        return;
+    };
+    if let Some(store) = world.get_resource::<PlayerStore>()
+        && let Err(e) = store.save(&record)
+    {
+        error!("Failed to save {}: {:?}", record.name, e);
+    }
+}
+
+/// Writes a new player's first character file, refusing to clobber an existing one
+pub fn create_character(world: &World, player: Entity) -> bool {
+    let Some(record) = snapshot(world, player) else {
+        return false;
+    };
+    let Some(store) = world.get_resource::<PlayerStore>() else {
+        return false;
+    };
+    match store.create(&record) {
+        Ok(()) => true,
+        Err(e) => {
+            error!("Failed to create {}: {:?}", record.name, e);
+            false
+        }
+    }
+}
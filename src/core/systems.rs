@@ -15,65 +15,160 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::core::{
-    components::{Location, Name, OutputTx, Player, Zone},
-    events::{BroadcastEvent, BroadcastRoomEvent, BroadcastZoneEvent, OutputEvent},
+    components::{
+        Attributes, Experience, Health, Ignoring, Location, Name, OutputTx, Player, PlayerState,
+        Race, Scrollback, Stamina, Zone,
+    },
+    effects::{EffectKind, Effects},
+    events::{
+        ApplyEffectEvent, BroadcastEvent, BroadcastRoomEvent, BroadcastZoneEvent, DamageEvent,
+        DeathEvent, EmoteEvent, ExperienceEvent, OutputEvent, SaveEvent,
+    },
+    progression::{MAX_LEVEL, next_level_xp},
+    race::RaceTrait,
+    storage::save_character,
+    visibility::can_see_in,
+    world::{GameTick, TICKS_PER_SECOND},
 };
 
 use bevy_ecs::prelude::*;
 
+/// Five minutes.
+const AUTOSAVE_TICKS: u64 = 5 * 60 * TICKS_PER_SECOND;
+const ACID_BURN_TICKS: u64 = 5 * TICKS_PER_SECOND;
+const ACID_BURN_DAMAGE: u32 = 2;
+
+/// A connected player as seen by the broadcast systems.
+type Listener<'a> = (
+    Entity,
+    Option<&'a Location>,
+    &'a OutputTx,
+    &'a mut Scrollback,
+    Option<&'a Ignoring>,
+);
+
+/// Anything that can take damage, and whether it's a player.
+type Victim<'a> = (
+    &'a mut Health,
+    Option<&'a Race>,
+    Option<&'a Location>,
+    Has<Player>,
+);
+
+/// Sends a line to a player's connection, remembering it in their scrollback
+pub fn deliver(tx: &OutputTx, scrollback: &mut Scrollback, text: &str) {
+    scrollback.push(text);
+    let _ = tx.0.send(format!("{}\r\n> ", text));
+}
+
+/// Whether `ignoring` hides messages sent by `from`
+fn ignores(ignoring: Option<&Ignoring>, names: &Query<&Name>, from: Entity) -> bool {
+    ignoring.is_some_and(|i| names.get(from).is_ok_and(|n| i.contains(&n.0)))
+}
+
 /// Broadcasts messages to all players except the sender
 pub fn flush_broadcasts(
-    players: Query<(Entity, &OutputTx, Option<&Name>), With<Player>>,
+    mut players: Query<Listener, (With<Player>, With<Name>)>,
     mut events: MessageReader<BroadcastEvent>,
 ) {
     for event in events.read() {
-        for (ent, tx, maybe_name) in players.iter() {
-            if ent == event.from {
-                continue;
-            }
-            if maybe_name.is_some() {
-                let _ = tx.0.send(format!("{}\r\n> ", event.text));
+        for (ent, _, tx, mut scrollback, _) in players.iter_mut() {
+            if ent != event.from {
+                deliver(tx, &mut scrollback, &event.text);
             }
         }
     }
 }
 
-/// Broadcasts messages to all players inside the same room except the sender
-pub fn flush_broadcasts_room(
-    players: Query<(Entity, &Location, &OutputTx, Option<&Name>), With<Player>>,
-    mut events: MessageReader<BroadcastRoomEvent>,
+/// Broadcasts messages to all players inside the same room except the sender,
+/// skipping anyone ignoring them
+pub fn flush_broadcasts_room(world: &mut World) {
+    let events: Vec<BroadcastRoomEvent> = world
+        .resource_mut::<Messages<BroadcastRoomEvent>>()
+        .drain()
+        .collect();
+    for event in events {
+        let unseen = event.unseen.as_deref();
+        deliver_to_room(world, event.from, None, event.room, &event.text, unseen);
+    }
+}
+
+/// Shows emotes to everyone in the room except the actor, their target and
+/// anyone ignoring the actor
+pub fn flush_emotes(world: &mut World) {
+    let events: Vec<EmoteEvent> = world
+        .resource_mut::<Messages<EmoteEvent>>()
+        .drain()
+        .collect();
+    for event in events {
+        let unseen = event.unseen.as_deref();
+        deliver_to_room(
+            world,
+            event.from,
+            event.target,
+            event.room,
+            &event.text,
+            unseen,
+        );
+    }
+}
+
+/// Delivers `text` from `from` to the other players in `room`, handing the
+/// `unseen` version to those who can't see the sender
+fn deliver_to_room(
+    world: &mut World,
+    from: Entity,
+    skip: Option<Entity>,
+    room: Entity,
+    text: &str,
+    unseen: Option<&str>,
 ) {
-    for event in events.read() {
-        for (ent, loc, tx, maybe_name) in players.iter() {
-            if ent == event.from {
-                continue;
-            }
-            if loc.0 == event.room {
-                if maybe_name.is_some() {
-                    let _ = tx.0.send(format!("{}\r\n> ", event.text));
-                }
+    let sender = world
+        .get::<Name>(from)
+        .map_or(String::new(), |n| n.0.clone());
+    let recipients: Vec<(Entity, String)> = world
+        .query_filtered::<(Entity, &Location, Option<&Ignoring>), (With<Player>, With<Name>)>()
+        .iter(world)
+        .filter(|(ent, loc, ignoring)| {
+            *ent != from
+                && Some(*ent) != skip
+                && loc.0 == room
+                && !ignoring.is_some_and(|i| i.contains(&sender))
+        })
+        .map(|(ent, _, _)| {
+            if can_see_in(world, ent, from, room) {
+                (ent, text.to_string())
+            } else {
+                (ent, unseen.unwrap_or(text).to_string())
             }
+        })
+        .collect();
+    let mut listeners = world.query::<(&OutputTx, &mut Scrollback)>();
+    for (ent, line) in recipients {
+        if let Ok((tx, mut scrollback)) = listeners.get_mut(world, ent) {
+            deliver(tx, &mut scrollback, &line);
         }
     }
 }
 
-/// Broadcasts messages to all players inside a zone except the sender
+/// Broadcasts messages to all players inside a zone except the sender,
+/// skipping anyone ignoring them
 pub fn flush_broadcasts_zone(
-    players: Query<(Entity, &Location, &OutputTx, Option<&Name>), With<Player>>,
+    mut players: Query<Listener, (With<Player>, With<Name>)>,
+    names: Query<&Name>,
+    zones: Query<&Zone>,
     mut events: MessageReader<BroadcastZoneEvent>,
-    world: &World,
 ) {
     for event in events.read() {
-        for (ent, loc, tx, maybe_name) in players.iter() {
-            if ent == event.from {
+        for (ent, loc, tx, mut scrollback, ignoring) in players.iter_mut() {
+            if ent == event.from || ignores(ignoring, &names, event.from) {
                 continue;
             }
-            if let Some(zone) = world.get::<Zone>(loc.0) {
-                if zone.0 == event.zone {
-                    if maybe_name.is_some() {
-                        let _ = tx.0.send(format!("{}\r\n> ", event.text));
-                    }
-                }
+            if let Some(loc) = loc
+                && let Ok(zone) = zones.get(loc.0)
+                && zone.0 == event.zone
+            {
+                deliver(tx, &mut scrollback, &event.text);
             }
         }
     }
@@ -81,12 +176,212 @@ pub fn flush_broadcasts_zone(
 
 /// Sends output messages to individual players
 pub fn flush_output(
-    mut players: Query<&OutputTx, With<Player>>,
+    mut players: Query<(&OutputTx, &mut Scrollback), With<Player>>,
     mut events: MessageReader<OutputEvent>,
 ) {
     for event in events.read() {
-        if let Ok(tx) = players.get_mut(event.player) {
-            let _ = tx.0.send(format!("{}\r\n> ", event.text));
+        if let Ok((tx, mut scrollback)) = players.get_mut(event.player) {
+            deliver(tx, &mut scrollback, &event.text);
+        }
+    }
+}
+
+/// Advances the fixed tick counter once per update
+pub fn advance_tick(mut tick: ResMut<GameTick>) {
+    tick.0 += 1;
+}
+
+/// Applies queued damage, splashing acid back on anyone who wounds a Xenomorph
+pub fn apply_damage(
+    mut events: MessageReader<DamageEvent>,
+    mut victims: Query<Victim>,
+    effects: Query<&Effects>,
+    mut output: MessageWriter<OutputEvent>,
+    mut experience: MessageWriter<ExperienceEvent>,
+    mut deaths: MessageWriter<DeathEvent>,
+    mut afflictions: MessageWriter<ApplyEffectEvent>,
+) {
+    for event in events.read() {
+        let weakened = event
+            .source
+            .and_then(|source| effects.get(source).ok())
+            .is_some_and(|e| e.has(EffectKind::Weakened));
+        let amount = if weakened {
+            event.amount / 2
+        } else {
+            event.amount
+        };
+        let (acid, room) = match victims.get_mut(event.target) {
+            Ok((mut health, race, loc, is_player)) => {
+                let before = health.current;
+                if before == 0 {
+                    continue;
+                }
+                health.current = health.current.saturating_sub(amount);
+                // Only damage to NPCs pays, so players can't farm each other.
+                if let Some(source) = event.source
+                    && source != event.target
+                    && !is_player
+                    && before > health.current
+                {
+                    experience.write(ExperienceEvent {
+                        player: source,
+                        amount: u64::from(before - health.current),
+                    });
+                }
+                output.write(OutputEvent {
+                    player: event.target,
+                    text: format!(
+                        "You take {} damage. ({}/{})",
+                        amount, health.current, health.max
+                    ),
+                });
+                if health.current == 0 {
+                    deaths.write(DeathEvent {
+                        victim: event.target,
+                        killer: event.source,
+                    });
+                }
+                (
+                    race.is_some_and(|r| r.has_trait(RaceTrait::AcidBlood)),
+                    loc.map(|l| l.0),
+                )
+            }
+            Err(_) => continue,
+        };
+
+        if !acid {
+            continue;
+        }
+        if let Some(source) = event.source
+            && source != event.target
+            && let Ok((mut health, source_race, loc, _)) = victims.get_mut(source)
+            && loc.map(|l| l.0) == room
+            && health.current > 0
+            && !source_race.is_some_and(|r| r.has_trait(RaceTrait::AcidBlood))
+        {
+            let splash = (amount / 2).max(1);
+            health.current = health.current.saturating_sub(splash);
+            output.write(OutputEvent {
+                player: source,
+                text: format!(
+                    "Acidic blood sprays over you, searing for {} damage! ({}/{})",
+                    splash, health.current, health.max
+                ),
+            });
+            if health.current == 0 {
+                deaths.write(DeathEvent {
+                    victim: source,
+                    killer: Some(event.target),
+                });
+            } else {
+                afflictions.write(ApplyEffectEvent {
+                    target: source,
+                    kind: EffectKind::AcidBurn,
+                    duration: ACID_BURN_TICKS,
+                    magnitude: ACID_BURN_DAMAGE,
+                    source: Some(event.target),
+                });
+            }
+        }
+    }
+}
+
+/// Restores one point of stamina every second
+pub fn regenerate_stamina(tick: Res<GameTick>, mut query: Query<&mut Stamina>) {
+    if !tick.0.is_multiple_of(TICKS_PER_SECOND) {
+        return;
+    }
+    for mut stamina in query.iter_mut() {
+        if stamina.current < stamina.max {
+            stamina.current += 1;
+        }
+    }
+}
+
+/// Grants experience, levelling characters up as they cross each threshold
+pub fn award_experience(
+    mut events: MessageReader<ExperienceEvent>,
+    mut query: Query<(
+        &mut Experience,
+        &mut Attributes,
+        &mut Health,
+        &Race,
+        Option<&Name>,
+    )>,
+    players: Query<(), With<Player>>,
+    mut output: MessageWriter<OutputEvent>,
+    mut broadcast: MessageWriter<BroadcastEvent>,
+    mut saves: MessageWriter<SaveEvent>,
+) {
+    for event in events.read() {
+        if !players.contains(event.player) {
+            continue;
+        }
+        let Ok((mut exp, mut attrs, mut health, race, name)) = query.get_mut(event.player) else {
+            continue;
+        };
+        exp.xp += event.amount;
+
+        let mut levelled = false;
+        while exp.level < MAX_LEVEL && exp.xp >= next_level_xp(exp.level) {
+            let (gains, health_gain) = race.level_gains();
+            exp.level += 1;
+            attrs.strength += gains.strength;
+            attrs.agility += gains.agility;
+            attrs.intellect += gains.intellect;
+            health.max += health_gain;
+            health.current = health.max;
+            levelled = true;
+
+            output.write(OutputEvent {
+                player: event.player,
+                text: format!(
+                    "You have reached level {}! (+{} strength, +{} agility, +{} intellect, +{} health)",
+                    exp.level, gains.strength, gains.agility, gains.intellect, health_gain
+                ),
+            });
+            if let Some(name) = name {
+                broadcast.write(BroadcastEvent {
+                    from: event.player,
+                    text: format!("{} has reached level {}.", name, exp.level),
+                });
+            }
+        }
+
+        if levelled {
+            saves.write(SaveEvent {
+                player: event.player,
+            });
         }
     }
 }
+
+/// Writes requested character saves, plus a periodic autosave of everyone online
+pub fn save_characters(world: &mut World) {
+    let mut players: Vec<Entity> = world
+        .resource_mut::<Messages<SaveEvent>>()
+        .drain()
+        .map(|e| e.player)
+        .collect();
+
+    if world
+        .resource::<GameTick>()
+        .0
+        .is_multiple_of(AUTOSAVE_TICKS)
+    {
+        let mut active = world.query::<(Entity, &PlayerState)>();
+        players.extend(
+            active
+                .iter(world)
+                .filter(|(_, state)| **state == PlayerState::Active)
+                .map(|(ent, _)| ent),
+        );
+    }
+
+    players.sort();
+    players.dedup();
+    for player in players {
+        save_character(world, player);
+    }
+}
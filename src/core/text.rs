@@ -0,0 +1,36 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::components::Name;
+
+use bevy_ecs::prelude::*;
+
+/// A character's name, or "Someone" for an entity that has none
+pub fn display_name(world: &World, ent: Entity) -> String {
+    match world.get::<Name>(ent) {
+        Some(n) => n.0.clone(),
+        None => "Someone".to_string(),
+    }
+}
+
+/// Upper-cases the first character, for item names that open a sentence
+pub fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,185 @@
+// Drakors
+// Copyright (C) 2025-present  Nathanael Bracy
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published
+// by the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::core::clock::{GameClock, TimeOfDay, is_outdoors};
+use crate::core::components::{
+    Admin, Cloaked, Health, HeldBy, Hidden, LightSource, Location, Race, RoomFlag,
+};
+use crate::core::race::RaceTrait;
+use crate::core::world::has_flag;
+
+use bevy_ecs::prelude::*;
+
+fn location_of(world: &World, ent: Entity) -> Option<Entity> {
+    world.get::<Location>(ent).map(|l| l.0)
+}
+
+fn has_vision(world: &World, viewer: Entity, vision: RaceTrait) -> bool {
+    world
+        .get::<Race>(viewer)
+        .is_some_and(|r| r.has_trait(vision))
+}
+
+/// Whether a light source is lying in `room` or carried by someone there
+pub fn is_lit(world: &World, room: Entity) -> bool {
+    if has_flag(world, room, RoomFlag::Lit) {
+        return true;
+    }
+    let Some(mut lights) =
+        world.try_query_filtered::<(Option<&Location>, Option<&HeldBy>), With<LightSource>>()
+    else {
+        return false;
+    };
+    lights.iter(world).any(|(loc, held)| {
+        loc.is_some_and(|l| l.0 == room)
+            || held.is_some_and(|h| location_of(world, h.0) == Some(room))
+    })
+}
+
+/// Whether `room` is too dark to see in without help: flagged dark, or outdoors at night, and unlit
+pub fn is_dark(world: &World, room: Entity) -> bool {
+    let night = world
+        .get_resource::<GameClock>()
+        .is_some_and(|c| c.time_of_day() == TimeOfDay::Night);
+    let dark = has_flag(world, room, RoomFlag::Dark) || (night && is_outdoors(world, room));
+    dark && !is_lit(world, room)
+}
+
+/// Whether `viewer` can make out the room itself: its description, items and scenery
+pub fn can_see_room(world: &World, viewer: Entity, room: Entity) -> bool {
+    !is_dark(world, room) || has_vision(world, viewer, RaceTrait::DarkVision)
+}
+
+/// Whether `viewer` can see `target` where it stands now.
+///
+/// Hiding fools everyone until `search` turns them up, and cloaks fool
+/// everyone but thermal vision. Darkness only matters when both stand in the
+/// same room: darkvision pierces it, and thermal vision picks out anything
+/// still alive. Admins see everything.
+pub fn can_see(world: &World, viewer: Entity, target: Entity) -> bool {
+    sees(world, viewer, target, location_of(world, target))
+}
+
+/// Whether `viewer` can see `target` in `room`, which may be a room the target
+/// has since left, such as the one its departure is announced in
+pub fn can_see_in(world: &World, viewer: Entity, target: Entity, room: Entity) -> bool {
+    sees(world, viewer, target, Some(room))
+}
+
+fn sees(world: &World, viewer: Entity, target: Entity, room: Option<Entity>) -> bool {
+    if viewer == target || world.get::<Admin>(viewer).is_some() {
+        return true;
+    }
+    if world.get::<Hidden>(target).is_some() {
+        return false;
+    }
+    let thermal = has_vision(world, viewer, RaceTrait::ThermalVision);
+    if world.get::<Cloaked>(target).is_some() && !thermal {
+        return false;
+    }
+    let (Some(room), Some(here)) = (room, location_of(world, viewer)) else {
+        return true;
+    };
+    if room != here {
+        return true;
+    }
+    if can_see_room(world, viewer, room) {
+        return true;
+    }
+    thermal && world.get::<Health>(target).is_some_and(|h| h.current > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::components::RoomFlags;
+    use crate::core::world::create_room;
+
+    fn alive() -> Health {
+        Health {
+            current: 10,
+            max: 10,
+        }
+    }
+
+    /// A dark room holding a human viewer and a living human target
+    fn dark_room() -> (World, Entity, Entity, Entity) {
+        let mut world = World::new();
+        let room = create_room(&mut world, "Pit", "");
+        world
+            .entity_mut(room)
+            .insert(RoomFlags([RoomFlag::Dark].into()));
+        let viewer = world.spawn((Race::Human, Location(room))).id();
+        let target = world.spawn((Race::Human, Location(room), alive())).id();
+        (world, room, viewer, target)
+    }
+
+    #[test]
+    fn darkness_hides_others_unless_lit() {
+        let (mut world, room, viewer, target) = dark_room();
+        assert!(!sees(&world, viewer, target, Some(room)));
+        assert!(sees(&world, viewer, viewer, Some(room)));
+
+        world.spawn((LightSource, HeldBy(target)));
+        assert!(sees(&world, viewer, target, Some(room)));
+    }
+
+    #[test]
+    fn darkness_only_matters_in_the_viewers_room() {
+        let (mut world, _, viewer, target) = dark_room();
+        let elsewhere = create_room(&mut world, "Hall", "");
+        assert!(sees(&world, viewer, target, Some(elsewhere)));
+    }
+
+    #[test]
+    fn darkvision_sees_in_the_dark() {
+        let (mut world, room, viewer, target) = dark_room();
+        world.entity_mut(viewer).insert(Race::Alien);
+        assert!(sees(&world, viewer, target, Some(room)));
+    }
+
+    #[test]
+    fn thermal_vision_sees_the_living_and_the_cloaked() {
+        let (mut world, room, viewer, target) = dark_room();
+        world.entity_mut(viewer).insert(Race::Predator);
+        world.entity_mut(target).insert(Cloaked);
+        assert!(sees(&world, viewer, target, Some(room)));
+
+        world.get_mut::<Health>(target).unwrap().current = 0;
+        assert!(!sees(&world, viewer, target, Some(room)));
+    }
+
+    #[test]
+    fn cloaks_fool_everyone_else() {
+        let (mut world, room, viewer, target) = dark_room();
+        world.entity_mut(room).insert(RoomFlags::default());
+        assert!(sees(&world, viewer, target, Some(room)));
+        world.entity_mut(target).insert(Cloaked);
+        assert!(!sees(&world, viewer, target, Some(room)));
+    }
+
+    #[test]
+    fn hiding_fools_even_thermal_vision_but_not_admins() {
+        let (mut world, room, viewer, target) = dark_room();
+        world.entity_mut(room).insert(RoomFlags::default());
+        world.entity_mut(viewer).insert(Race::Predator);
+        world.entity_mut(target).insert(Hidden { difficulty: 0 });
+        assert!(!sees(&world, viewer, target, Some(room)));
+
+        world.entity_mut(viewer).insert(Admin);
+        assert!(sees(&world, viewer, target, Some(room)));
+    }
+}
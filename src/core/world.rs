@@ -14,35 +14,100 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
+use std::path::PathBuf;
 
-use crate::core::components::Room;
+use crate::core::clock::{TimeOfDay, TimedDescriptions};
+use crate::core::components::{
+    ConcealedExits, Doors, Extra, Extras, HeldBy, Item, Landmark, LightSource, Location, Placed,
+    Room, RoomFlag, RoomFlags, Value, keywords_match,
+};
+use crate::core::dialogue::{DialogueDef, DialogueRegistry};
+use crate::core::economy::Shop;
+use crate::core::mail::{Board, BoardDef};
+use crate::core::npc::{Npc, NpcDef, NpcTemplates, respawn_missing, spawn_npc};
+use crate::core::quests::{QuestDef, QuestRegistry};
+use crate::core::scripting::{ScriptEngine, TriggerDefs};
 
 use anyhow::Context;
 use bevy_ecs::prelude::World;
 use bevy_ecs::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use toml;
+use tracing::{info, warn};
 
 #[derive(Resource, Default)]
 pub struct RoomRegistry {
     pub id_to_entity: HashMap<String, Entity>,
+    pub entity_to_id: HashMap<Entity, String>,
 }
 
 impl RoomRegistry {
     pub fn insert(&mut self, id: String, ent: Entity) {
+        self.entity_to_id.insert(ent, id.clone());
         self.id_to_entity.insert(id, ent);
     }
 
     pub fn get(&self, id: &str) -> Option<Entity> {
         self.id_to_entity.get(id).copied()
     }
+
+    pub fn id_of(&self, ent: Entity) -> Option<&str> {
+        self.entity_to_id.get(&ent).map(String::as_str)
+    }
+}
+
+/// Number of fixed ticks the server has run since startup.
+#[derive(Resource, Default)]
+pub struct GameTick(pub u64);
+
+/// Fixed ticks the server runs each second.
+pub const TICKS_PER_SECOND: u64 = 20;
+
+/// Ticks between resets for a zone that resets every `minutes`, never less than one minute
+pub fn reset_ticks(minutes: u64) -> u64 {
+    minutes.max(1) * 60 * TICKS_PER_SECOND
+}
+
+/// Registry id of the room players return to after death, unless their zone overrides it.
+#[derive(Resource)]
+pub struct RespawnPoint(pub String);
+
+impl Default for RespawnPoint {
+    fn default() -> Self {
+        Self("default:start".to_string())
+    }
 }
 
 #[derive(Resource, Default)]
 pub struct ZoneRegistry {
     pub id_to_name: HashMap<String, String>,
+    pub respawns: HashMap<String, String>,
+    /// Ticks between resets, per zone.
+    pub resets: HashMap<String, u64>,
+    /// Directory zone files are read from and new zones are written to.
+    pub dir: PathBuf,
+    /// Each zone's definition as last loaded or saved.
+    pub sources: HashMap<String, ZoneSource>,
+    /// Zones edited in-game since they were last saved.
+    pub dirty: HashSet<String>,
+}
+
+/// A zone's file and the definition it holds.
+#[derive(Debug, Clone)]
+pub struct ZoneSource {
+    pub path: PathBuf,
+    pub def: ZoneDef,
+}
+
+/// Prefixes a zone-local id with its zone unless it already names one
+pub fn qualify(zone: &str, id: &str) -> String {
+    if id.contains(':') {
+        id.to_string()
+    } else {
+        format!("{}:{}", zone, id)
+    }
 }
 
 pub fn create_room(world: &mut World, name: &str, description: &str) -> Entity {
@@ -52,28 +117,165 @@ pub fn create_room(world: &mut World, name: &str, description: &str) -> Entity {
         exits: HashMap::new(),
     };
 
-    let ent = world.spawn((room,)).id();
+    world.spawn((room,)).id()
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ItemDef {
+    pub name: String,
+    pub keywords: Vec<String>,
+    pub description: String,
+    /// Base price; zero means shops won't trade it.
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub value: u32,
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub light: bool,
+}
+
+impl ItemDef {
+    pub fn matches(&self, word: &str) -> bool {
+        keywords_match(&self.keywords, word)
+    }
+}
+
+/// Spawns an item from its definition, carried by `holder`
+pub fn spawn_item(world: &mut World, def: &ItemDef, holder: Entity) -> Entity {
+    let ent = world
+        .spawn((
+            Item {
+                name: def.name.clone(),
+                keywords: def.keywords.clone(),
+                description: def.description.clone(),
+            },
+            HeldBy(holder),
+        ))
+        .id();
+    if def.value > 0 {
+        world.entity_mut(ent).insert(Value(def.value));
+    }
+    if def.light {
+        world.entity_mut(ent).insert(LightSource);
+    }
+    ent
+}
+
+/// Spawns a zone-file item lying in `room`, tagged with `key` for resets
+pub fn place_item(world: &mut World, def: &ItemDef, room: Entity, key: String) -> Entity {
+    let ent = spawn_item(world, def, room);
+    world
+        .entity_mut(ent)
+        .remove::<HeldBy>()
+        .insert((Location(room), Placed(key)));
     ent
 }
 
-#[derive(Deserialize, Debug)]
+fn close_doors(dirs: &[String]) -> Doors {
+    Doors(dirs.iter().map(|dir| (dir.clone(), true)).collect())
+}
+
+fn conceal_exits(dirs: &[String]) -> ConcealedExits {
+    ConcealedExits(dirs.iter().cloned().collect())
+}
+
+/// Puts back any zone-file items in a room that have been taken or destroyed
+fn restore_items(world: &mut World, room_id: &str, room: Entity, items: &[ItemDef]) {
+    let present: HashSet<String> = world
+        .query::<&Placed>()
+        .iter(world)
+        .map(|p| p.0.clone())
+        .collect();
+    for (i, def) in items.iter().enumerate() {
+        let key = format!("{}#{}", room_id, i);
+        if !present.contains(&key) {
+            place_item(world, def, room, key);
+        }
+    }
+}
+
+/// Scenery a player can `look` at by keyword.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ExtraDef {
+    pub keywords: Vec<String>,
+    pub description: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct RoomDef {
     pub id: String,
     pub name: String,
     pub description: String,
-    #[serde(default)]
-    pub exits: HashMap<String, String>,
+    /// Name players can `travel` to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub landmark: Option<String>,
+    /// Exits fitted with a door, closed when the zone loads and on each reset.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub doors: Vec<String>,
+    /// Exits that stay out of sight until someone finds them with `search`, again after each reset.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_exits: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<RoomFlag>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub exits: BTreeMap<String, String>,
+    /// Replaces `description` at certain times of day.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub time_descriptions: BTreeMap<TimeOfDay, String>,
+    #[serde(default, skip_serializing_if = "TriggerDefs::is_empty")]
+    pub triggers: TriggerDefs,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board: Option<BoardDef>,
+    /// Items lying in the room when the zone loads.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub items: Vec<ItemDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<ExtraDef>,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ZoneDef {
     pub id: String,
     pub name: String,
+    /// Local room id where players who die in this zone respawn.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub respawn: Option<String>,
+    /// Minutes between resets, which respawn NPCs and restock shops.
+    #[serde(
+        default = "default_reset_minutes",
+        skip_serializing_if = "is_default_reset_minutes"
+    )]
+    pub reset_minutes: u64,
     pub rooms: Vec<RoomDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub npcs: Vec<NpcDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dialogues: Vec<DialogueDef>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quests: Vec<QuestDef>,
+}
+
+pub fn default_reset_minutes() -> u64 {
+    10
+}
+
+fn is_default_reset_minutes(minutes: &u64) -> bool {
+    *minutes == default_reset_minutes()
+}
+
+/// Whether `room` carries `flag`
+pub fn has_flag(world: &World, room: Entity, flag: RoomFlag) -> bool {
+    world.get::<RoomFlags>(room).is_some_and(|f| f.has(flag))
+}
+
+/// Lets saved zone files leave out fields that still hold their default
+pub fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
 }
 
 pub fn load_zones_from_dir(world: &mut World, dir: &str) -> anyhow::Result<()> {
     let paths = fs::read_dir(dir).with_context(|| format!("reading zone dir {}", dir))?;
+    world.resource_mut::<ZoneRegistry>().dir = PathBuf::from(dir);
+    // Exits into other zones, resolved once every zone has registered its rooms.
+    let mut foreign_exits: Vec<(Entity, String, String)> = Vec::new();
 
     for entry in paths {
         let entry = entry?;
@@ -93,20 +295,106 @@ pub fn load_zones_from_dir(world: &mut World, dir: &str) -> anyhow::Result<()> {
                 .entity_mut(ent)
                 .insert(crate::core::components::Zone(zone.id.clone()));
             local_map.insert(r.id.clone(), ent);
+            if let Some(landmark) = &r.landmark {
+                world.entity_mut(ent).insert(Landmark(landmark.clone()));
+            }
+            if !r.doors.is_empty() {
+                world.entity_mut(ent).insert(close_doors(&r.doors));
+            }
+            if !r.hidden_exits.is_empty() {
+                world.entity_mut(ent).insert(conceal_exits(&r.hidden_exits));
+            }
+            if !r.time_descriptions.is_empty() {
+                let timed = r.time_descriptions.clone().into_iter().collect();
+                world.entity_mut(ent).insert(TimedDescriptions(timed));
+            }
+            if !r.flags.is_empty() {
+                let flags = RoomFlags(r.flags.iter().copied().collect());
+                world.entity_mut(ent).insert(flags);
+            }
+            if !r.extras.is_empty() {
+                let extras = r
+                    .extras
+                    .iter()
+                    .map(|e| Extra {
+                        keywords: e.keywords.clone(),
+                        description: e.description.clone(),
+                    })
+                    .collect();
+                world.entity_mut(ent).insert(Extras(extras));
+            }
+
+            if let Some(board) = &r.board {
+                world.spawn((
+                    Board {
+                        id: qualify(&zone.id, &r.id),
+                        name: board.name.clone(),
+                        description: board.description.clone(),
+                    },
+                    Location(ent),
+                ));
+            }
+
+            if !r.triggers.is_empty() {
+                let owner = format!("{}:{}", zone.id, r.id);
+                let triggers = world
+                    .resource_mut::<ScriptEngine>()
+                    .compile_triggers(&owner, &r.triggers);
+                world.entity_mut(ent).insert(triggers);
+            }
         }
 
         for r in &zone.rooms {
-            if let Some(&ent) = local_map.get(&r.id) {
-                if let Some(mut room) = world.get_mut::<crate::core::components::Room>(ent) {
-                    for (dir_str, target_id) in &r.exits {
-                        if let Some(&target_ent) = local_map.get(target_id) {
-                            room.exits.insert(dir_str.clone(), target_ent);
-                        }
+            if let Some(&ent) = local_map.get(&r.id)
+                && let Some(mut room) = world.get_mut::<crate::core::components::Room>(ent)
+            {
+                for (dir_str, target_id) in &r.exits {
+                    if let Some(&target_ent) = local_map.get(target_id) {
+                        room.exits.insert(dir_str.clone(), target_ent);
+                    } else if target_id.contains(':') {
+                        foreign_exits.push((ent, dir_str.clone(), target_id.clone()));
                     }
                 }
             }
         }
 
+        for r in &zone.rooms {
+            if let Some(&ent) = local_map.get(&r.id) {
+                restore_items(world, &qualify(&zone.id, &r.id), ent, &r.items);
+            }
+        }
+
+        let mut dialogues = world.resource_mut::<DialogueRegistry>();
+        for dialogue in &zone.dialogues {
+            dialogues
+                .0
+                .insert(format!("{}:{}", zone.id, dialogue.id), dialogue.clone());
+        }
+
+        let mut quests = world.resource_mut::<QuestRegistry>();
+        for quest in &zone.quests {
+            quests.0.insert(
+                format!("{}:{}", zone.id, quest.id),
+                quest.clone().qualified(&zone.id),
+            );
+        }
+
+        for npc in &zone.npcs {
+            match local_map.get(&npc.room) {
+                Some(&room) => {
+                    spawn_npc(world, &zone.id, npc, room);
+                    world.resource_mut::<NpcTemplates>().0.insert(
+                        format!("{}:{}", zone.id, npc.id),
+                        (zone.id.clone(), npc.clone(), room),
+                    );
+                }
+                None => warn!(
+                    "NPC {}:{} placed in unknown room {}",
+                    zone.id, npc.id, npc.room
+                ),
+            }
+        }
+
         let mut reg = world.resource_mut::<RoomRegistry>();
         for (id, ent) in local_map {
             let reg_id = format!("{}:{}", zone.id, id);
@@ -115,7 +403,302 @@ pub fn load_zones_from_dir(world: &mut World, dir: &str) -> anyhow::Result<()> {
 
         let mut zin = world.resource_mut::<ZoneRegistry>();
         zin.id_to_name.insert(zone.id.clone(), zone.name.clone());
+        zin.resets
+            .insert(zone.id.clone(), reset_ticks(zone.reset_minutes));
+        if let Some(respawn) = &zone.respawn {
+            zin.respawns
+                .insert(zone.id.clone(), format!("{}:{}", zone.id, respawn));
+        }
+        zin.sources.insert(
+            zone.id.clone(),
+            ZoneSource {
+                path: path.clone(),
+                def: zone,
+            },
+        );
+    }
+
+    for (ent, dir, target_id) in foreign_exits {
+        let target = world.resource::<RoomRegistry>().get(&target_id);
+        match (target, world.get_mut::<Room>(ent)) {
+            (Some(target), Some(mut room)) => {
+                room.exits.insert(dir, target);
+            }
+            _ => warn!("Exit {} leads to unknown room {}", dir, target_id),
+        }
     }
 
     Ok(())
 }
+
+/// Rebuilds a zone's definition from the live world, keeping whatever the world doesn't track
+pub fn zone_to_def(world: &World, zone_id: &str) -> Option<ZoneDef> {
+    let mut def = world
+        .resource::<ZoneRegistry>()
+        .sources
+        .get(zone_id)?
+        .def
+        .clone();
+    let registry = world.resource::<RoomRegistry>();
+    let prefix = format!("{}:", zone_id);
+    let local_id = |ent: Entity| -> Option<String> {
+        let id = registry.id_of(ent)?;
+        Some(id.strip_prefix(&prefix).unwrap_or(id).to_string())
+    };
+
+    let mut live: Vec<(String, Entity)> = registry
+        .id_to_entity
+        .iter()
+        .filter_map(|(id, ent)| Some((id.strip_prefix(&prefix)?.to_string(), *ent)))
+        .collect();
+    // Rooms keep their place in the file; new ones follow in id order.
+    live.sort_by_key(|(id, _)| {
+        let pos = def.rooms.iter().position(|r| &r.id == id);
+        (pos.unwrap_or(usize::MAX), id.clone())
+    });
+
+    let mut boards = world.try_query::<(&Board, &Location)>()?;
+    let rooms = live
+        .into_iter()
+        .filter_map(|(id, ent)| {
+            let room = world.get::<Room>(ent)?;
+            let source = def.rooms.iter().find(|r| r.id == id);
+            let board = boards
+                .iter(world)
+                .find(|(_, loc)| loc.0 == ent)
+                .map(|(b, _)| BoardDef {
+                    name: b.name.clone(),
+                    description: b.description.clone(),
+                });
+            let mut doors: Vec<String> = world
+                .get::<Doors>(ent)
+                .map(|d| d.0.keys().cloned().collect())
+                .unwrap_or_default();
+            doors.retain(|dir| room.exits.contains_key(dir));
+            doors.sort();
+            let mut flags: Vec<RoomFlag> = world
+                .get::<RoomFlags>(ent)
+                .map(|f| f.0.iter().copied().collect())
+                .unwrap_or_default();
+            flags.sort();
+            // Exits found by searching drop out of the live set until the next reset,
+            // so the file's list is kept and anything concealed since is added.
+            let mut hidden_exits: Vec<String> =
+                source.map(|r| r.hidden_exits.clone()).unwrap_or_default();
+            let mut concealed: Vec<String> = world
+                .get::<ConcealedExits>(ent)
+                .map(|c| c.0.iter().cloned().collect())
+                .unwrap_or_default();
+            concealed.sort();
+            for dir in concealed {
+                if !hidden_exits.contains(&dir) {
+                    hidden_exits.push(dir);
+                }
+            }
+            hidden_exits.retain(|dir| room.exits.contains_key(dir));
+            Some(RoomDef {
+                exits: room
+                    .exits
+                    .iter()
+                    .filter_map(|(dir, target)| Some((dir.clone(), local_id(*target)?)))
+                    .collect(),
+                id,
+                name: room.name.clone(),
+                description: room.description.clone(),
+                time_descriptions: source
+                    .map(|r| r.time_descriptions.clone())
+                    .unwrap_or_default(),
+                landmark: world.get::<Landmark>(ent).map(|l| l.0.clone()),
+                doors,
+                hidden_exits,
+                flags,
+                triggers: source.map(|r| r.triggers.clone()).unwrap_or_default(),
+                board,
+                items: source.map(|r| r.items.clone()).unwrap_or_default(),
+                extras: source.map(|r| r.extras.clone()).unwrap_or_default(),
+            })
+        })
+        .collect();
+    def.rooms = rooms;
+
+    let templates = world.resource::<NpcTemplates>();
+    for npc in &mut def.npcs {
+        if let Some((_, _, home)) = templates.0.get(&qualify(zone_id, &npc.id))
+            && let Some(id) = local_id(*home)
+        {
+            npc.room = id;
+        }
+    }
+    Some(def)
+}
+
+/// Writes a zone's live state back to its file and marks it clean
+pub fn save_zone(world: &mut World, zone_id: &str) -> anyhow::Result<PathBuf> {
+    let def = zone_to_def(world, zone_id).with_context(|| format!("unknown zone {}", zone_id))?;
+    let contents = toml::to_string(&def)?;
+    let mut zones = world.resource_mut::<ZoneRegistry>();
+    let path = match zones.sources.get(zone_id) {
+        Some(source) => source.path.clone(),
+        None => zones.dir.join(format!("{}.toml", zone_id)),
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("creating {:?}", dir))?;
+    }
+    fs::write(&path, contents).with_context(|| format!("writing {:?}", path))?;
+    zones.sources.insert(
+        zone_id.to_string(),
+        ZoneSource {
+            path: path.clone(),
+            def,
+        },
+    );
+    zones.dirty.remove(zone_id);
+    Ok(path)
+}
+
+/// Resets every zone whose interval has elapsed: dead NPCs and taken items return, doors close,
+/// hidden exits are hidden again and shops restock
+pub fn reset_zones(world: &mut World) {
+    let tick = world.resource::<GameTick>().0;
+    if tick == 0 {
+        return;
+    }
+    let due: Vec<String> = world
+        .resource::<ZoneRegistry>()
+        .resets
+        .iter()
+        .filter(|(_, interval)| tick.is_multiple_of(**interval))
+        .map(|(zone, _)| zone.clone())
+        .collect();
+
+    for zone in due {
+        respawn_missing(world, &zone);
+        let rooms: Vec<RoomDef> = world
+            .resource::<ZoneRegistry>()
+            .sources
+            .get(&zone)
+            .map(|source| {
+                source
+                    .def
+                    .rooms
+                    .iter()
+                    .filter(|r| {
+                        !r.items.is_empty() || !r.doors.is_empty() || !r.hidden_exits.is_empty()
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        for def in rooms {
+            let room_id = qualify(&zone, &def.id);
+            if let Some(room) = world.resource::<RoomRegistry>().get(&room_id) {
+                restore_items(world, &room_id, room, &def.items);
+                if !def.doors.is_empty() {
+                    world.entity_mut(room).insert(close_doors(&def.doors));
+                }
+                if !def.hidden_exits.is_empty() {
+                    world
+                        .entity_mut(room)
+                        .insert(conceal_exits(&def.hidden_exits));
+                }
+            }
+        }
+        let prefix = format!("{}:", zone);
+        for (npc, mut shop) in world.query::<(&Npc, &mut Shop)>().iter_mut(world) {
+            if npc.template.starts_with(&prefix) {
+                shop.restock();
+            }
+        }
+        info!("Zone {} reset", zone);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::olc::remove_exit;
+    use crate::core::scripting::ScriptEngine;
+
+    fn empty_world() -> World {
+        let mut world = World::new();
+        world.insert_resource(RoomRegistry::default());
+        world.insert_resource(ZoneRegistry::default());
+        world.insert_resource(DialogueRegistry::default());
+        world.insert_resource(QuestRegistry::default());
+        world.insert_resource(NpcTemplates::default());
+        world.insert_resource(GameTick::default());
+        world.insert_resource(ScriptEngine::new());
+        world
+    }
+
+    /// Room names and exits by registry id, independent of entity numbering
+    fn layout(world: &World) -> BTreeMap<String, (String, BTreeMap<String, String>)> {
+        let registry = world.resource::<RoomRegistry>();
+        registry
+            .id_to_entity
+            .iter()
+            .map(|(id, ent)| {
+                let room = world.get::<Room>(*ent).unwrap();
+                let exits = room
+                    .exits
+                    .iter()
+                    .map(|(dir, target)| (dir.clone(), registry.id_of(*target).unwrap().into()))
+                    .collect();
+                (id.clone(), (room.name.clone(), exits))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn zone_round_trip() {
+        let dir = std::env::temp_dir().join(format!("drakors-zones-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::copy("lib/zones/default.toml", dir.join("default.toml")).unwrap();
+        let dir_str = dir.to_str().unwrap();
+
+        let mut first = empty_world();
+        load_zones_from_dir(&mut first, dir_str).unwrap();
+        let written = fs::read_to_string(dir.join("default.toml")).unwrap();
+        let original: ZoneDef = toml::from_str(&written).unwrap();
+        let saved = toml::to_string(&zone_to_def(&first, "default").unwrap()).unwrap();
+        assert_eq!(toml::to_string(&original).unwrap(), saved);
+
+        save_zone(&mut first, "default").unwrap();
+        let mut second = empty_world();
+        load_zones_from_dir(&mut second, dir_str).unwrap();
+        let reloaded = toml::to_string(&zone_to_def(&second, "default").unwrap()).unwrap();
+
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(saved, reloaded);
+        assert_eq!(layout(&first), layout(&second));
+    }
+
+    #[test]
+    fn removed_exit_round_trip() {
+        let dir = std::env::temp_dir().join(format!("drakors-removed-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::copy("lib/zones/default.toml", dir.join("default.toml")).unwrap();
+        let dir_str = dir.to_str().unwrap();
+
+        let mut first = empty_world();
+        load_zones_from_dir(&mut first, dir_str).unwrap();
+        let registry = first.resource::<RoomRegistry>();
+        let start = registry.get("default:start").unwrap();
+        let corridor = registry.get("default:corridor").unwrap();
+        assert!(remove_exit(&mut first, start, "east"));
+        assert!(remove_exit(&mut first, corridor, "east"));
+        save_zone(&mut first, "default").unwrap();
+
+        let mut second = empty_world();
+        load_zones_from_dir(&mut second, dir_str).unwrap();
+        let def = zone_to_def(&second, "default").unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        let room = |id: &str| def.rooms.iter().find(|r| r.id == id).unwrap();
+        assert!(!room("start").exits.contains_key("east"));
+        assert!(!room("start").doors.contains(&"east".to_string()));
+        assert!(!room("corridor").exits.contains_key("east"));
+        assert!(room("corridor").hidden_exits.is_empty());
+        assert_eq!(layout(&first), layout(&second));
+    }
+}
@@ -18,12 +18,19 @@ mod core;
 mod network;
 mod server;
 
-use server::app::run_server;
+use server::app::{export_dot, run_server};
 
 use tracing_subscriber::EnvFilter;
 
 #[tokio::main(flavor = "multi_thread", worker_threads = 4)]
 async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("export-dot") {
+        let dir = args.get(2).map_or("lib/zones", String::as_str);
+        print!("{}", export_dot(dir)?);
+        return Ok(());
+    }
+
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
 
     tracing_subscriber::fmt().with_env_filter(env_filter).init();
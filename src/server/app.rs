@@ -16,14 +16,34 @@
 
 use crate::core::CommandMap;
 use crate::core::CorePlugin;
+use crate::core::channels::Channels;
+use crate::core::clock::load_clock;
 use crate::core::commands::CommandScope;
+use crate::core::commands::items::items_held_by;
 use crate::core::components::{
-    Location, Name, OutputTx, Player, PlayerState, Race, RegistrationData,
+    Admin, Builder, Cooldowns, Credits, Experience, Flags, Frozen, Ignoring, Location, Name,
+    OutputTx, PasswordHash, Player, PlayerState, Race, RegistrationData, Scrollback, VisitedRooms,
 };
+use crate::core::dialogue::DialogueRegistry;
+use crate::core::economy::{STARTING_CREDITS, TransactionLog};
+use crate::core::effects::Effects;
 use crate::core::events::{
     BroadcastEvent, BroadcastRoomEvent, CommandEvent, DisconnectEvent, OutputEvent,
 };
-use crate::core::world::{RoomRegistry, ZoneRegistry, load_zones_from_dir};
+use crate::core::groups::part_company;
+use crate::core::input::{InputModes, route_line};
+use crate::core::mail::notify_mail;
+use crate::core::mapping::to_dot;
+use crate::core::moderation::ReportLog;
+use crate::core::npc::NpcTemplates;
+use crate::core::password::verify_password;
+use crate::core::quests::{QuestLog, QuestRegistry};
+use crate::core::scripting::{TriggerKind, fire};
+use crate::core::socials::{Socials, load_socials};
+use crate::core::storage::{
+    BoardStore, MailStore, PlayerStore, create_character, restore, save_character, validate_name,
+};
+use crate::core::world::{RoomRegistry, TICKS_PER_SECOND, ZoneRegistry, load_zones_from_dir};
 use crate::network::connection::start_networking;
 
 use bevy_app::App;
@@ -50,6 +70,53 @@ const RACE_PROMPT : &str = "Choose your race:
 
 Your choice [A/H/P/S]: ";
 
+fn name_in_use(world: &mut World, name: &str) -> bool {
+    world
+        .query_filtered::<&Name, With<Player>>()
+        .iter(world)
+        .any(|n| n.0.eq_ignore_ascii_case(name))
+}
+
+/// Announces a freshly logged-in character to everyone else
+fn enter_game(world: &mut World, player: Entity, name: &str, greeting: String) {
+    world.write_message(BroadcastEvent {
+        from: player,
+        text: format!("{} has joined the game.", name),
+    });
+
+    if let Some(room) = world.get::<Location>(player).map(|l| l.0) {
+        world.write_message(BroadcastRoomEvent {
+            from: player,
+            room,
+            text: format!("{} appears in a bright flash of light.", name),
+            unseen: Some("Someone appears in a bright flash of light.".to_string()),
+        });
+    }
+
+    world.write_message(OutputEvent {
+        player,
+        text: greeting,
+    });
+    notify_mail(world, player);
+}
+
+/// Registries filled in by `load_zones_from_dir`
+fn add_world_registries(app: &mut App) -> &mut App {
+    app.insert_resource(RoomRegistry::default())
+        .insert_resource(ZoneRegistry::default())
+        .insert_resource(DialogueRegistry::default())
+        .insert_resource(QuestRegistry::default())
+        .insert_resource(NpcTemplates::default())
+}
+
+/// Loads the zones in `dir` and renders their room graph as Graphviz DOT, without starting the server
+pub fn export_dot(dir: &str) -> anyhow::Result<String> {
+    let mut app = App::new();
+    add_world_registries(app.add_plugins(CorePlugin));
+    load_zones_from_dir(app.world_mut(), dir)?;
+    Ok(to_dot(app.world()))
+}
+
 pub async fn run_server(addr: &str) -> anyhow::Result<()> {
     let (command_tx, command_rx) = mpsc::unbounded_channel::<CommandEvent>();
     let (disconnect_tx, disconnect_rx) = mpsc::unbounded_channel::<DisconnectEvent>();
@@ -69,21 +136,31 @@ pub async fn run_server(addr: &str) -> anyhow::Result<()> {
     });
 
     let mut app = App::new();
-    app.add_plugins(CorePlugin)
+    add_world_registries(app.add_plugins(CorePlugin))
         .insert_resource(CommandQueue(command_rx))
         .insert_resource(DisconnectQueue(disconnect_rx))
-        .insert_resource(RoomRegistry::default())
-        .insert_resource(ZoneRegistry::default());
+        .insert_resource(PlayerStore::new("lib/players"))
+        .insert_resource(MailStore::new("lib/mail"))
+        .insert_resource(BoardStore::new("lib/boards"))
+        .insert_resource(TransactionLog::new("lib/logs/transactions.log"))
+        .insert_resource(ReportLog::new("lib/logs/reports.log"));
 
     {
         let world = app.world_mut();
         if let Err(e) = load_zones_from_dir(world, "lib/zones") {
             error!("Failed to load zones: {:?}", e);
         }
+        if let Err(e) = load_clock(world, "lib/clock.toml") {
+            error!("Failed to load clock: {:?}", e);
+        }
+        world.init_resource::<Socials>();
+        if let Err(e) = load_socials(world, "lib/socials.toml") {
+            error!("Failed to load socials: {:?}", e);
+        }
     }
     info!("Drakors starting on {}", addr);
 
-    let tick_duration = std::time::Duration::from_millis(50); // 20 ticks per second (50ms per tick)
+    let tick_duration = std::time::Duration::from_millis(1000 / TICKS_PER_SECOND);
     let mut tick_timer = tokio::time::interval(tick_duration);
 
     loop {
@@ -94,21 +171,24 @@ pub async fn run_server(addr: &str) -> anyhow::Result<()> {
                     let mut q = app.world_mut().resource_mut::<CommandQueue>();
                     q.0.try_recv()
                 } {
+                    if route_line(app.world_mut(), event.player, event.input.trim_end()) {
+                        continue;
+                    }
+
                     let input = event.input.trim().to_string();
 
                     if let Some(state) = app.world().get::<PlayerState>(event.player) {
                         match *state {
                             PlayerState::ChoosingName => {
-                                let existing: Vec<String> = {
-                                    let world = app.world_mut();
-                                    world
-                                        .query::<&Name>()
-                                        .iter(&world)
-                                        .map(|n| n.0.clone())
-                                        .collect()
-                                };
+                                if let Err(msg) = validate_name(&input) {
+                                    app.world_mut().write_message(OutputEvent {
+                                        player: event.player,
+                                        text: format!("{} Please enter your name: ", msg),
+                                    });
+                                    continue;
+                                }
 
-                                if existing.iter().any(|n| n == &input) {
+                                if name_in_use(app.world_mut(), &input) {
                                     app.world_mut().write_message(OutputEvent {
                                         player: event.player,
                                         text: "Name already in use, please pick another: ".to_string(),
@@ -117,8 +197,56 @@ pub async fn run_server(addr: &str) -> anyhow::Result<()> {
                                     if let Some(mut reg) = app.world_mut().get_mut::<RegistrationData>(event.player) {
                                         reg.chosen_name = Some(input.clone());
                                     }
-                                    app.world_mut().entity_mut(event.player).insert(PlayerState::ChoosingPassword);
-                                    app.world_mut().write_message(OutputEvent { player: event.player, text: "Please enter a password: ".to_string() });
+                                    if app.world().resource::<PlayerStore>().exists(&input) {
+                                        app.world_mut().entity_mut(event.player).insert(PlayerState::EnteringPassword);
+                                        app.world_mut().write_message(OutputEvent { player: event.player, text: "Welcome back. Password: ".to_string() });
+                                    } else {
+                                        app.world_mut().entity_mut(event.player).insert(PlayerState::ChoosingPassword);
+                                        app.world_mut().write_message(OutputEvent { player: event.player, text: "Please enter a password: ".to_string() });
+                                    }
+                                }
+                                continue;
+                            }
+                            PlayerState::EnteringPassword => {
+                                let name = app.world().get::<RegistrationData>(event.player)
+                                    .and_then(|r| r.chosen_name.clone())
+                                    .unwrap_or_default();
+                                let loaded = app.world().resource::<PlayerStore>().load(&name);
+
+                                match loaded {
+                                    Ok(record) if verify_password(&input, &record.password_hash) => {
+                                        if name_in_use(app.world_mut(), &record.name) {
+                                            app.world_mut().entity_mut(event.player).insert(PlayerState::ChoosingName);
+                                            app.world_mut().write_message(OutputEvent {
+                                                player: event.player,
+                                                text: "That character is already playing.\r\nPlease enter your name: ".to_string(),
+                                            });
+                                            continue;
+                                        }
+                                        let name = record.name.clone();
+                                        let world = app.world_mut();
+                                        restore(world, event.player, record);
+                                        world.entity_mut(event.player).insert((Cooldowns::default(), PlayerState::Active));
+                                        if let Some(mut w) = world.get_mut::<RegistrationData>(event.player) {
+                                            let _ = std::mem::take(&mut *w);
+                                        }
+                                        enter_game(world, event.player, &name, format!("Welcome back, {}!", name));
+                                    }
+                                    Ok(_) => {
+                                        app.world_mut().entity_mut(event.player).insert(PlayerState::ChoosingName);
+                                        app.world_mut().write_message(OutputEvent {
+                                            player: event.player,
+                                            text: "Incorrect password.\r\nPlease enter your name: ".to_string(),
+                                        });
+                                    }
+                                    Err(e) => {
+                                        error!("Failed to load character {}: {:?}", name, e);
+                                        app.world_mut().entity_mut(event.player).insert(PlayerState::ChoosingName);
+                                        app.world_mut().write_message(OutputEvent {
+                                            player: event.player,
+                                            text: "That character could not be loaded.\r\nPlease enter your name: ".to_string(),
+                                        });
+                                    }
                                 }
                                 continue;
                             }
@@ -148,35 +276,72 @@ pub async fn run_server(addr: &str) -> anyhow::Result<()> {
                                 };
 
                                 if let Some(race) = race_opt {
-                                    let name = {
-                                        app.world().get::<RegistrationData>(event.player)
-                                            .and_then(|r| r.chosen_name.clone())
-                                            .unwrap_or_else(|| "Someone".to_string())
+                                    let (name, password_hash) = {
+                                        let reg = app.world().get::<RegistrationData>(event.player);
+                                        (
+                                            reg.and_then(|r| r.chosen_name.clone())
+                                                .unwrap_or_else(|| "Someone".to_string()),
+                                            reg.and_then(|r| r.password_hash.clone())
+                                                .unwrap_or_default(),
+                                        )
                                     };
-                                    app.world_mut().entity_mut(event.player).insert(Name(name.clone()));
-                                    app.world_mut().entity_mut(event.player).insert(race.clone());
-                                    app.world_mut().entity_mut(event.player).insert(PlayerState::Active);
-
-                                    app.world_mut().write_message(BroadcastEvent {
-                                        from: event.player,
-                                        text: format!("{} has joined the game.", name.clone()),
-                                    });
-
-                                    let maybe_room = {
-                                        app.world().get::<Location>(event.player).map(|l| l.0)
+                                    if name_in_use(app.world_mut(), &name) || app.world().resource::<PlayerStore>().exists(&name) {
+                                        app.world_mut().entity_mut(event.player).insert(PlayerState::ChoosingName);
+                                        app.world_mut().write_message(OutputEvent {
+                                            player: event.player,
+                                            text: "That name was taken while you were creating your character.\r\nPlease enter your name: ".to_string(),
+                                        });
+                                        continue;
+                                    }
+                                    let visited: VisitedRooms = {
+                                        let world = app.world();
+                                        let room_id = world.get::<Location>(event.player)
+                                            .and_then(|l| world.resource::<RoomRegistry>().id_of(l.0))
+                                            .map(str::to_string);
+                                        VisitedRooms(room_id.into_iter().collect())
                                     };
-                                    if let Some(room) = maybe_room {
-                                        app.world_mut().write_message(BroadcastRoomEvent {
-                                            from: event.player,
-                                            room,
-                                            text: format!("{} appears in a bright flash of light.", name.clone()),
+                                    app.world_mut().entity_mut(event.player).insert(Name(name.clone()));
+                                    app.world_mut().entity_mut(event.player).insert((
+                                        race,
+                                        race.base_attributes(),
+                                        race.base_health(),
+                                        race.base_stamina(),
+                                        Cooldowns::default(),
+                                        Effects::default(),
+                                        Experience::default(),
+                                        Flags::default(),
+                                        QuestLog::default(),
+                                        Credits(STARTING_CREDITS),
+                                        Ignoring::default(),
+                                        Channels::defaults(Some(race), false),
+                                        PasswordHash(password_hash),
+                                        visited,
+                                    ));
+                                    if !create_character(app.world(), event.player) {
+                                        app.world_mut().entity_mut(event.player).remove::<(Name, PasswordHash)>();
+                                        app.world_mut().entity_mut(event.player).insert(PlayerState::ChoosingName);
+                                        app.world_mut().write_message(OutputEvent {
+                                            player: event.player,
+                                            text: "That character could not be created.\r\nPlease enter your name: ".to_string(),
                                         });
+                                        continue;
                                     }
+                                    app.world_mut().entity_mut(event.player).insert(PlayerState::Active);
 
-                                    app.world_mut().write_message(OutputEvent {
-                                        player: event.player,
-                                        text: format!("Welcome, {}!", name.clone()),
-                                    });
+                                    let attrs = race.base_attributes();
+                                    enter_game(
+                                        app.world_mut(),
+                                        event.player,
+                                        &name,
+                                        format!(
+                                            "Welcome, {} the {}!\nStrength {}, Agility {}, Intellect {}.",
+                                            name.clone(),
+                                            race.label(),
+                                            attrs.strength,
+                                            attrs.agility,
+                                            attrs.intellect
+                                        ),
+                                    );
 
                                     if let Some(mut w) = app.world_mut().get_mut::<RegistrationData>(event.player) {
                                         let _ = std::mem::take(&mut *w);
@@ -198,6 +363,30 @@ pub async fn run_server(addr: &str) -> anyhow::Result<()> {
                     };
                     let args: Vec<&str> = words;
 
+                    if command_name != "quit" && app.world().get::<Frozen>(event.player).is_some() {
+                        app.world_mut().write_message(OutputEvent {
+                            player: event.player,
+                            text: "You are frozen solid and cannot act.".to_string(),
+                        });
+                        continue;
+                    }
+
+                    let active = matches!(app.world().get::<PlayerState>(event.player), Some(PlayerState::Active));
+                    let room = app.world().get::<Location>(event.player).map(|l| l.0);
+                    if active && let Some(room) = room {
+                        let script_args: rhai::Array = args.iter().map(|a| a.to_string().into()).collect();
+                        let handled = fire(
+                            app.world_mut(),
+                            room,
+                            Some(event.player),
+                            TriggerKind::Command,
+                            &[("command", command_name.clone().into()), ("args", script_args.into())],
+                        );
+                        if handled {
+                            continue;
+                        }
+                    }
+
                     let (handler_opt, scope_opt) = {
                         let map = app.world().resource::<CommandMap>();
                         (
@@ -213,6 +402,15 @@ pub async fn run_server(addr: &str) -> anyhow::Result<()> {
                             CommandScope::Active => {
                                 matches!(player_state, Some(PlayerState::Active))
                             }
+                            CommandScope::Admin => {
+                                matches!(player_state, Some(PlayerState::Active))
+                                    && app.world().get::<Admin>(event.player).is_some()
+                            }
+                            CommandScope::Builder => {
+                                matches!(player_state, Some(PlayerState::Active))
+                                    && (app.world().get::<Builder>(event.player).is_some()
+                                        || app.world().get::<Admin>(event.player).is_some())
+                            }
                         };
 
                         if allowed {
@@ -233,6 +431,7 @@ pub async fn run_server(addr: &str) -> anyhow::Result<()> {
 
                 // Handle disconnects
                 while let Ok(event) = app.world_mut().resource_mut::<DisconnectQueue>().0.try_recv() {
+                    save_character(app.world(), event.player);
                     let maybe_name = app.world().get::<Name>(event.player).map(|n| n.0.clone());
                     if let Some(name) = maybe_name {
                         app.world_mut().write_message(BroadcastEvent {
@@ -240,6 +439,10 @@ pub async fn run_server(addr: &str) -> anyhow::Result<()> {
                             text: format!("{} has left the game.", name),
                         });
                     }
+                    part_company(app.world_mut(), event.player);
+                    for (item, _) in items_held_by(app.world_mut(), event.player) {
+                        app.world_mut().despawn(item);
+                    }
                     app.world_mut().despawn(event.player);
                 }
 
@@ -247,7 +450,7 @@ pub async fn run_server(addr: &str) -> anyhow::Result<()> {
                 while let Ok((tx, resp)) = register_rx.try_recv() {
                     let w = app.world_mut();
                     let entity = w
-                        .spawn((Player, OutputTx(tx.clone()), PlayerState::ChoosingName, RegistrationData::default()))
+                        .spawn((Player, OutputTx(tx.clone()), Scrollback::default(), InputModes::default(), PlayerState::ChoosingName, RegistrationData::default()))
                         .id();
 
                     if let Some(start) = w.resource::<RoomRegistry>().get("default:start") {